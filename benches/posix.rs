@@ -1,5 +1,5 @@
-use criterion::{criterion_group, criterion_main, Criterion};
-use nodejs_path::{posix};
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use nodejs_path::posix;
 
 fn criterion_benchmark(c: &mut Criterion) {
     c.bench_function("join_impl", |b| {
@@ -58,10 +58,49 @@ fn criterion_benchmark(c: &mut Criterion) {
             }
         })
     });
+    let specifiers = (0..1_000)
+        .map(|i| match i % 4 {
+            0 => format!("./src/module_{}.js", i),
+            1 => format!("../shared/{}/index.js", i),
+            2 => format!("node_modules/pkg_{}/lib/./main.js", i),
+            _ => format!("/abs/path/{}.js", i),
+        })
+        .collect::<Vec<_>>();
+    let base = "/home/user/project/packages/app/src/components";
+    c.bench_function("resolve! x1000", |b| {
+        b.iter(|| {
+            for specifier in &specifiers {
+                let _res = posix::resolve!(base, specifier);
+            }
+        })
+    });
+    c.bench_function("BaseDir::resolve_many x1000", |b| {
+        let mut base_dir = posix::BaseDir::new(base);
+        b.iter(|| {
+            base_dir.resolve_many(&specifiers, |resolved| {
+                black_box(resolved);
+            })
+        })
+    });
+    c.bench_function("relative x1000", |b| {
+        b.iter(|| {
+            for specifier in &specifiers {
+                let _res = posix::relative(base, specifier);
+            }
+        })
+    });
+    c.bench_function("BaseDir::relative_many x1000", |b| {
+        let mut base_dir = posix::BaseDir::new(base);
+        b.iter(|| {
+            base_dir.relative_many(&specifiers, |relative| {
+                black_box(relative);
+            })
+        })
+    });
     c.bench_function("cwd", |b| {
         b.iter(|| {
-          posix::cwd();
-      })
+            posix::cwd();
+        })
     });
 }
 
@@ -0,0 +1,12 @@
+fn main() {
+    // The C tests are linked into this crate only, and driven by `tests/c.rs`.
+    println!("cargo:rerun-if-changed=nodejs_path_test.c");
+    println!("cargo:rerun-if-changed=../include/nodejs_path.h");
+    cc::Build::new()
+        .file("nodejs_path_test.c")
+        .include("../include")
+        .warnings(true)
+        .extra_warnings(true)
+        .warnings_into_errors(true)
+        .compile("nodejs_path_c_test");
+}
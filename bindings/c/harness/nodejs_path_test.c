@@ -0,0 +1,94 @@
+#include <stdio.h>
+#include <string.h>
+
+#include "nodejs_path.h"
+
+static int failures = 0;
+
+static NodePathStr str(const char *s) {
+  NodePathStr out = {(const uint8_t *)s, strlen(s)};
+  return out;
+}
+
+static void expect_string(const char *label, NodePathString actual, const char *expected) {
+  if (actual.ptr == NULL || actual.len != strlen(expected) || memcmp(actual.ptr, expected, actual.len) != 0) {
+    fprintf(stderr, "%s: expected \"%s\", got \"%.*s\"\n", label, expected, (int)actual.len,
+            actual.ptr == NULL ? "(null)" : (const char *)actual.ptr);
+    failures++;
+  }
+  nodejs_path_string_free(actual);
+}
+
+static void expect_bool(const char *label, bool actual, bool expected) {
+  if (actual != expected) {
+    fprintf(stderr, "%s: expected %d, got %d\n", label, expected, actual);
+    failures++;
+  }
+}
+
+static void test_normalize(void) {
+  expect_string("normalize", nodejs_path_posix_normalize(str("/foo/bar//baz/asdf/quux/..")), "/foo/bar/baz/asdf");
+  expect_string("normalize empty", nodejs_path_posix_normalize(str("")), ".");
+
+  NodePathStr null_input = {NULL, 0};
+  expect_string("normalize null", nodejs_path_posix_normalize(null_input), ".");
+
+  const uint8_t invalid[] = {'/', 0xff, 'a'};
+  NodePathStr invalid_input = {invalid, sizeof(invalid)};
+  NodePathString result = nodejs_path_posix_normalize(invalid_input);
+  if (result.ptr != NULL) {
+    fprintf(stderr, "normalize invalid utf-8: expected NULL\n");
+    failures++;
+  }
+  nodejs_path_string_free(result);
+}
+
+static void test_join_resolve_relative(void) {
+  NodePathStr segments[] = {str("/foo"), str("bar"), str("baz/asdf"), str("quux"), str("..")};
+  expect_string("join", nodejs_path_posix_join(segments, 5), "/foo/bar/baz/asdf");
+  expect_string("join none", nodejs_path_posix_join(NULL, 0), ".");
+
+  NodePathStr resolve[] = {str("/foo/bar"), str("./baz")};
+  expect_string("resolve", nodejs_path_posix_resolve(resolve, 2), "/foo/bar/baz");
+  NodePathStr resolve_abs[] = {str("/foo/bar"), str("/tmp/file/")};
+  expect_string("resolve absolute", nodejs_path_posix_resolve(resolve_abs, 2), "/tmp/file");
+
+  expect_string("relative",
+                nodejs_path_posix_relative(str("/data/orandea/test/aaa"), str("/data/orandea/impl/bbb")),
+                "../../impl/bbb");
+  expect_string("relative same", nodejs_path_posix_relative(str("/var/lib"), str("/var/lib")), "");
+}
+
+static void test_components(void) {
+  expect_string("dirname", nodejs_path_posix_dirname(str("/foo/bar/baz/asdf/quux")), "/foo/bar/baz/asdf");
+  expect_string("basename", nodejs_path_posix_basename(str("/foo/quux.html"), str("")), "quux.html");
+  expect_string("basename ext", nodejs_path_posix_basename(str("/foo/quux.html"), str(".html")), "quux");
+  expect_string("extname", nodejs_path_posix_extname(str("index.coffee.md")), ".md");
+  expect_bool("posix is_absolute", nodejs_path_posix_is_absolute(str("/foo")), true);
+  expect_bool("posix is_absolute relative", nodejs_path_posix_is_absolute(str("foo")), false);
+  expect_bool("win32 is_absolute", nodejs_path_win32_is_absolute(str("C:\\foo")), true);
+  expect_bool("win32 is_absolute drive relative", nodejs_path_win32_is_absolute(str("C:foo")), false);
+}
+
+static void test_parse_format(void) {
+  NodePathParsed parsed = nodejs_path_posix_parse(str("/home/user/dir/file.txt"));
+  NodePathParsedRef ref = {
+      {parsed.root.ptr, parsed.root.len}, {parsed.dir.ptr, parsed.dir.len},   {parsed.base.ptr, parsed.base.len},
+      {parsed.ext.ptr, parsed.ext.len},   {parsed.name.ptr, parsed.name.len},
+  };
+  expect_string("format(parse())", nodejs_path_posix_format(ref), "/home/user/dir/file.txt");
+  expect_bool("parse root", parsed.root.len == 1 && parsed.root.ptr[0] == '/', true);
+  expect_bool("parse ext", parsed.ext.len == 4 && memcmp(parsed.ext.ptr, ".txt", 4) == 0, true);
+  nodejs_path_parsed_free(parsed);
+
+  NodePathParsedRef partial = {{NULL, 0}, str("some/dir"), {NULL, 0}, str(".html"), str("index")};
+  expect_string("format", nodejs_path_posix_format(partial), "some/dir/index.html");
+}
+
+int nodejs_path_c_tests(void) {
+  test_normalize();
+  test_join_resolve_relative();
+  test_components();
+  test_parse_format();
+  return failures;
+}
@@ -0,0 +1,2 @@
+// Links `nodejs_path_c`, whose exports the C tests in `nodejs_path_test.c` call.
+pub use nodejs_path_c::*;
@@ -0,0 +1,19 @@
+use std::os::raw::c_int;
+
+// Make sure the exported functions are linked into the test binary.
+#[allow(unused_imports)]
+use nodejs_path_c_harness::*;
+
+extern "C" {
+    // Defined in `nodejs_path_test.c`, compiled by `build.rs`.
+    fn nodejs_path_c_tests() -> c_int;
+}
+
+#[test]
+fn c_tests() {
+    assert_eq!(
+        unsafe { nodejs_path_c_tests() },
+        0,
+        "see stderr for failures"
+    );
+}
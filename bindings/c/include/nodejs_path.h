@@ -0,0 +1,148 @@
+#ifndef NODEJS_PATH_H
+#define NODEJS_PATH_H
+
+/* Generated by cbindgen from bindings/c/src/lib.rs. Do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * An owned UTF-8 buffer allocated by this library. Release it with `nodejs_path_string_free`.
+ */
+typedef struct NodePathString {
+  uint8_t *ptr;
+  size_t len;
+} NodePathString;
+
+/**
+ * The result of `nodejs_path_posix_parse`. Release it with `nodejs_path_parsed_free`.
+ */
+typedef struct NodePathParsed {
+  struct NodePathString root;
+  struct NodePathString dir;
+  struct NodePathString base;
+  struct NodePathString ext;
+  struct NodePathString name;
+} NodePathParsed;
+
+/**
+ * A borrowed UTF-8 buffer.
+ */
+typedef struct NodePathStr {
+  const uint8_t *ptr;
+  size_t len;
+} NodePathStr;
+
+/**
+ * The input of `nodejs_path_posix_format`. Any field may be `{ NULL, 0 }`.
+ */
+typedef struct NodePathParsedRef {
+  struct NodePathStr root;
+  struct NodePathStr dir;
+  struct NodePathStr base;
+  struct NodePathStr ext;
+  struct NodePathStr name;
+} NodePathParsedRef;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Releases a string returned by this library. Passing a `NULL` `ptr` is a no-op.
+ *
+ * # Safety
+ * `s` must have been returned by this library and not freed before.
+ */
+void nodejs_path_string_free(struct NodePathString s);
+
+/**
+ * Releases every field of a `NodePathParsed`.
+ *
+ * # Safety
+ * `parsed` must have been returned by `nodejs_path_posix_parse` and not freed before.
+ */
+void nodejs_path_parsed_free(struct NodePathParsed parsed);
+
+/**
+ * # Safety
+ * `path.ptr` must point to `path.len` readable bytes.
+ */
+struct NodePathString nodejs_path_posix_normalize(struct NodePathStr path);
+
+/**
+ * # Safety
+ * `paths` must point to `count` valid `NodePathStr`s.
+ */
+struct NodePathString nodejs_path_posix_join(const struct NodePathStr *paths, size_t count);
+
+/**
+ * # Safety
+ * `paths` must point to `count` valid `NodePathStr`s.
+ */
+struct NodePathString nodejs_path_posix_resolve(const struct NodePathStr *paths, size_t count);
+
+/**
+ * # Safety
+ * `from.ptr` and `to.ptr` must point to `from.len` and `to.len` readable bytes.
+ */
+struct NodePathString nodejs_path_posix_relative(struct NodePathStr from, struct NodePathStr to);
+
+/**
+ * # Safety
+ * `path.ptr` must point to `path.len` readable bytes.
+ */
+struct NodePathString nodejs_path_posix_dirname(struct NodePathStr path);
+
+/**
+ * Pass `{ NULL, 0 }` as `ext` to keep the extension.
+ *
+ * # Safety
+ * `path.ptr` and `ext.ptr` must point to `path.len` and `ext.len` readable bytes.
+ */
+struct NodePathString nodejs_path_posix_basename(struct NodePathStr path, struct NodePathStr ext);
+
+/**
+ * # Safety
+ * `path.ptr` must point to `path.len` readable bytes.
+ */
+struct NodePathString nodejs_path_posix_extname(struct NodePathStr path);
+
+/**
+ * Every field is `NULL` if `path` is not valid UTF-8.
+ *
+ * # Safety
+ * `path.ptr` must point to `path.len` readable bytes.
+ */
+struct NodePathParsed nodejs_path_posix_parse(struct NodePathStr path);
+
+/**
+ * # Safety
+ * Every field of `path_object` must point to as many readable bytes as its `len`.
+ */
+struct NodePathString nodejs_path_posix_format(struct NodePathParsedRef path_object);
+
+/**
+ * Returns `false` if `path` is not valid UTF-8.
+ *
+ * # Safety
+ * `path.ptr` must point to `path.len` readable bytes.
+ */
+bool nodejs_path_posix_is_absolute(struct NodePathStr path);
+
+/**
+ * Returns `false` if `path` is not valid UTF-8.
+ *
+ * # Safety
+ * `path.ptr` must point to `path.len` readable bytes.
+ */
+bool nodejs_path_win32_is_absolute(struct NodePathStr path);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* NODEJS_PATH_H */
@@ -0,0 +1,258 @@
+//! # Description
+//!
+//! C ABI for `nodejs_path`, so non-Rust consumers get the same path semantics as the Rust and Node tools.
+//! The header `include/nodejs_path.h` is generated by cbindgen and checked in. `tests/header.rs` fails when it is
+//! out of date with the exports, and running it with `NODEJS_PATH_C_UPDATE_HEADER=1` regenerates it.
+//!
+//! Strings cross the boundary as explicit length-prefixed UTF-8 buffers and are never NUL-terminated:
+//! - Inputs are borrowed [`NodePathStr`]s. `{ NULL, 0 }` is the empty string.
+//! - Outputs are owned [`NodePathString`]s that must be released with [`nodejs_path_string_free`]. A `NULL` `ptr`
+//!   means an input was not valid UTF-8.
+//!
+//! Only the methods ported in `nodejs_path::win32` are exported for win32.
+
+use std::{ptr, slice, str};
+
+use nodejs_path::{posix, win32, Parsed};
+
+/// A borrowed UTF-8 buffer.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct NodePathStr {
+    pub ptr: *const u8,
+    pub len: usize,
+}
+
+/// An owned UTF-8 buffer allocated by this library. Release it with `nodejs_path_string_free`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct NodePathString {
+    pub ptr: *mut u8,
+    pub len: usize,
+}
+
+/// The result of `nodejs_path_posix_parse`. Release it with `nodejs_path_parsed_free`.
+#[repr(C)]
+#[derive(Debug)]
+pub struct NodePathParsed {
+    pub root: NodePathString,
+    pub dir: NodePathString,
+    pub base: NodePathString,
+    pub ext: NodePathString,
+    pub name: NodePathString,
+}
+
+/// The input of `nodejs_path_posix_format`. Any field may be `{ NULL, 0 }`.
+#[repr(C)]
+#[derive(Debug, Clone, Copy)]
+pub struct NodePathParsedRef {
+    pub root: NodePathStr,
+    pub dir: NodePathStr,
+    pub base: NodePathStr,
+    pub ext: NodePathStr,
+    pub name: NodePathStr,
+}
+
+impl NodePathStr {
+    unsafe fn as_str<'a>(self) -> Option<&'a str> {
+        if self.ptr.is_null() || self.len == 0 {
+            Some("")
+        } else {
+            str::from_utf8(slice::from_raw_parts(self.ptr, self.len)).ok()
+        }
+    }
+}
+
+impl NodePathString {
+    fn null() -> Self {
+        Self {
+            ptr: ptr::null_mut(),
+            len: 0,
+        }
+    }
+}
+
+impl From<String> for NodePathString {
+    fn from(s: String) -> Self {
+        let boxed = s.into_bytes().into_boxed_slice();
+        let len = boxed.len();
+        Self {
+            ptr: Box::into_raw(boxed) as *mut u8,
+            len,
+        }
+    }
+}
+
+impl From<Option<String>> for NodePathString {
+    fn from(s: Option<String>) -> Self {
+        s.map_or_else(Self::null, Self::from)
+    }
+}
+
+unsafe fn collect_strs<'a>(paths: *const NodePathStr, count: usize) -> Option<Vec<&'a str>> {
+    if paths.is_null() || count == 0 {
+        Some(vec![])
+    } else {
+        slice::from_raw_parts(paths, count)
+            .iter()
+            .map(|path| path.as_str())
+            .collect()
+    }
+}
+
+/// Releases a string returned by this library. Passing a `NULL` `ptr` is a no-op.
+///
+/// # Safety
+/// `s` must have been returned by this library and not freed before.
+#[no_mangle]
+pub unsafe extern "C" fn nodejs_path_string_free(s: NodePathString) {
+    if !s.ptr.is_null() {
+        drop(Box::from_raw(ptr::slice_from_raw_parts_mut(s.ptr, s.len)));
+    }
+}
+
+/// Releases every field of a `NodePathParsed`.
+///
+/// # Safety
+/// `parsed` must have been returned by `nodejs_path_posix_parse` and not freed before.
+#[no_mangle]
+pub unsafe extern "C" fn nodejs_path_parsed_free(parsed: NodePathParsed) {
+    nodejs_path_string_free(parsed.root);
+    nodejs_path_string_free(parsed.dir);
+    nodejs_path_string_free(parsed.base);
+    nodejs_path_string_free(parsed.ext);
+    nodejs_path_string_free(parsed.name);
+}
+
+/// # Safety
+/// `path.ptr` must point to `path.len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn nodejs_path_posix_normalize(path: NodePathStr) -> NodePathString {
+    path.as_str().map(posix::normalize).into()
+}
+
+/// # Safety
+/// `paths` must point to `count` valid `NodePathStr`s.
+#[no_mangle]
+pub unsafe extern "C" fn nodejs_path_posix_join(
+    paths: *const NodePathStr,
+    count: usize,
+) -> NodePathString {
+    collect_strs(paths, count)
+        .map(|paths| posix::join_impl(&paths))
+        .into()
+}
+
+/// # Safety
+/// `paths` must point to `count` valid `NodePathStr`s.
+#[no_mangle]
+pub unsafe extern "C" fn nodejs_path_posix_resolve(
+    paths: *const NodePathStr,
+    count: usize,
+) -> NodePathString {
+    collect_strs(paths, count)
+        .map(|paths| posix::resolve_impl(&paths))
+        .into()
+}
+
+/// # Safety
+/// `from.ptr` and `to.ptr` must point to `from.len` and `to.len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn nodejs_path_posix_relative(
+    from: NodePathStr,
+    to: NodePathStr,
+) -> NodePathString {
+    from.as_str()
+        .zip(to.as_str())
+        .map(|(from, to)| posix::relative(from, to))
+        .into()
+}
+
+/// # Safety
+/// `path.ptr` must point to `path.len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn nodejs_path_posix_dirname(path: NodePathStr) -> NodePathString {
+    path.as_str().map(posix::dirname).into()
+}
+
+/// Pass `{ NULL, 0 }` as `ext` to keep the extension.
+///
+/// # Safety
+/// `path.ptr` and `ext.ptr` must point to `path.len` and `ext.len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn nodejs_path_posix_basename(
+    path: NodePathStr,
+    ext: NodePathStr,
+) -> NodePathString {
+    path.as_str()
+        .zip(ext.as_str())
+        .map(|(path, ext)| posix::basename_impl_without_ext(path, ext))
+        .into()
+}
+
+/// # Safety
+/// `path.ptr` must point to `path.len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn nodejs_path_posix_extname(path: NodePathStr) -> NodePathString {
+    path.as_str().map(posix::extname).into()
+}
+
+/// Every field is `NULL` if `path` is not valid UTF-8.
+///
+/// # Safety
+/// `path.ptr` must point to `path.len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn nodejs_path_posix_parse(path: NodePathStr) -> NodePathParsed {
+    match path.as_str().map(posix::parse) {
+        Some(parsed) => NodePathParsed {
+            root: parsed.root.into(),
+            dir: parsed.dir.into(),
+            base: parsed.base.into(),
+            ext: parsed.ext.into(),
+            name: parsed.name.into(),
+        },
+        None => NodePathParsed {
+            root: NodePathString::null(),
+            dir: NodePathString::null(),
+            base: NodePathString::null(),
+            ext: NodePathString::null(),
+            name: NodePathString::null(),
+        },
+    }
+}
+
+/// # Safety
+/// Every field of `path_object` must point to as many readable bytes as its `len`.
+#[no_mangle]
+pub unsafe extern "C" fn nodejs_path_posix_format(
+    path_object: NodePathParsedRef,
+) -> NodePathString {
+    (|| {
+        Some(posix::format(Parsed {
+            root: path_object.root.as_str()?.to_owned(),
+            dir: path_object.dir.as_str()?.to_owned(),
+            base: path_object.base.as_str()?.to_owned(),
+            ext: path_object.ext.as_str()?.to_owned(),
+            name: path_object.name.as_str()?.to_owned(),
+        }))
+    })()
+    .into()
+}
+
+/// Returns `false` if `path` is not valid UTF-8.
+///
+/// # Safety
+/// `path.ptr` must point to `path.len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn nodejs_path_posix_is_absolute(path: NodePathStr) -> bool {
+    path.as_str().map(posix::is_absolute).unwrap_or(false)
+}
+
+/// Returns `false` if `path` is not valid UTF-8.
+///
+/// # Safety
+/// `path.ptr` must point to `path.len` readable bytes.
+#[no_mangle]
+pub unsafe extern "C" fn nodejs_path_win32_is_absolute(path: NodePathStr) -> bool {
+    path.as_str().map(win32::is_absolute).unwrap_or(false)
+}
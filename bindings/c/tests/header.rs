@@ -0,0 +1,27 @@
+use std::{env, fs, path::PathBuf};
+
+// `include/nodejs_path.h` is checked in. Run with `NODEJS_PATH_C_UPDATE_HEADER=1` to regenerate it after changing
+// the exports.
+#[test]
+fn header_is_up_to_date() {
+    let crate_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let header_path = crate_dir.join("include/nodejs_path.h");
+
+    let mut generated = Vec::new();
+    cbindgen::Builder::new()
+        .with_crate(&crate_dir)
+        .with_config(cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).unwrap())
+        .generate()
+        .expect("Unable to generate nodejs_path.h")
+        .write(&mut generated);
+    let generated = String::from_utf8(generated).unwrap();
+
+    if env::var_os("NODEJS_PATH_C_UPDATE_HEADER").is_some() {
+        fs::write(&header_path, &generated).unwrap();
+        return;
+    }
+    assert!(
+        fs::read_to_string(&header_path).unwrap() == generated,
+        "include/nodejs_path.h is stale, rerun with NODEJS_PATH_C_UPDATE_HEADER=1"
+    );
+}
@@ -0,0 +1,68 @@
+'use strict';
+
+// Compares the addon with the built-in `path` module. Run with
+// `cargo build -p nodejs_path_node && node --test __test__/`.
+
+const test = require('node:test');
+const assert = require('node:assert');
+const path = require('path');
+
+const rusty = require('..');
+
+const paths = [
+  '',
+  '.',
+  '..',
+  '/',
+  '//',
+  '///a',
+  './',
+  '/foo/bar//baz/asdf/quux/..',
+  '/foo///bar.baz',
+  'foo/bar/',
+  '../../x/./y',
+  '/home/user/dir/file.txt',
+  '.bashrc',
+  '.bashrc.',
+  'index.coffee.md',
+  'C:\\temp\\myfile.html',
+];
+
+test('posix methods match node', () => {
+  for (const p of paths) {
+    assert.strictEqual(rusty.posix.normalize(p), path.posix.normalize(p), `normalize(${JSON.stringify(p)})`);
+    assert.strictEqual(rusty.posix.dirname(p), path.posix.dirname(p), `dirname(${JSON.stringify(p)})`);
+    assert.strictEqual(rusty.posix.basename(p), path.posix.basename(p), `basename(${JSON.stringify(p)})`);
+    assert.strictEqual(rusty.posix.extname(p), path.posix.extname(p), `extname(${JSON.stringify(p)})`);
+    assert.strictEqual(rusty.posix.isAbsolute(p), path.posix.isAbsolute(p), `isAbsolute(${JSON.stringify(p)})`);
+    assert.deepStrictEqual(rusty.posix.parse(p), path.posix.parse(p), `parse(${JSON.stringify(p)})`);
+  }
+});
+
+test('variadic signatures', () => {
+  assert.strictEqual(rusty.posix.join(), path.posix.join());
+  assert.strictEqual(rusty.posix.join('/foo', 'bar', 'baz/asdf', 'quux', '..'), '/foo/bar/baz/asdf');
+  assert.strictEqual(rusty.posix.resolve(), path.posix.resolve());
+  assert.strictEqual(rusty.posix.resolve('/foo/bar', './baz'), '/foo/bar/baz');
+  assert.strictEqual(rusty.posix.resolve('a/b/c/', '../../..'), path.posix.resolve('a/b/c/', '../../..'));
+  assert.strictEqual(rusty.posix.basename('/foo/quux.html', '.html'), 'quux');
+  assert.strictEqual(rusty.posix.relative('/data/orandea/test/aaa', '/data/orandea/impl/bbb'), '../../impl/bbb');
+});
+
+test('format accepts partial objects', () => {
+  assert.strictEqual(rusty.posix.format({ dir: 'some/dir' }), path.posix.format({ dir: 'some/dir' }));
+  assert.strictEqual(rusty.posix.format({ root: '/', name: 'index', ext: '.html' }), '/index.html');
+  assert.strictEqual(rusty.posix.format({}), '');
+  assert.strictEqual(rusty.posix.format(rusty.posix.parse('/home/user/dir/file.txt')), '/home/user/dir/file.txt');
+});
+
+test('constants and flavors', () => {
+  assert.strictEqual(rusty.posix.sep, path.posix.sep);
+  assert.strictEqual(rusty.posix.delimiter, path.posix.delimiter);
+  assert.strictEqual(rusty.win32.sep, path.win32.sep);
+  assert.strictEqual(rusty.win32.delimiter, path.win32.delimiter);
+  assert.strictEqual(rusty.posix.win32, rusty.win32);
+  for (const p of ['//server', '\\\\server', 'C:/foo/..', 'C:\\foo\\..', 'bar\\baz', 'C:', '.']) {
+    assert.strictEqual(rusty.win32.isAbsolute(p), path.win32.isAbsolute(p), `isAbsolute(${JSON.stringify(p)})`);
+  }
+});
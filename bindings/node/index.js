@@ -0,0 +1,60 @@
+'use strict';
+
+// Loads the addon built by `cargo build -p nodejs_path_node` and restores the
+// variadic signatures of Node's `path` module on top of the raw exports.
+
+const { existsSync } = require('fs');
+const { join } = require('path');
+
+function loadBinding() {
+  if (process.env.NODEJS_PATH_NODE_BINDING) {
+    return require(process.env.NODEJS_PATH_NODE_BINDING);
+  }
+  const local = join(__dirname, 'nodejs_path.node');
+  if (existsSync(local)) {
+    return require(local);
+  }
+  const libName = {
+    darwin: 'libnodejs_path_node.dylib',
+    win32: 'nodejs_path_node.dll',
+  }[process.platform] || 'libnodejs_path_node.so';
+  for (const profile of ['release', 'debug']) {
+    const built = join(__dirname, '..', '..', 'target', profile, libName);
+    if (existsSync(built)) {
+      const module = { exports: {} };
+      process.dlopen(module, built);
+      return module.exports;
+    }
+  }
+  throw new Error('nodejs_path_node is not built, run `cargo build -p nodejs_path_node` first');
+}
+
+const binding = loadBinding();
+
+const posix = {
+  sep: binding.posix.sep(),
+  delimiter: binding.posix.delimiter(),
+  basename: (path, ext) => binding.posix.basename(path, ext),
+  dirname: (path) => binding.posix.dirname(path),
+  extname: (path) => binding.posix.extname(path),
+  format: (pathObject) => binding.posix.format(pathObject),
+  isAbsolute: (path) => binding.posix.isAbsolute(path),
+  join: (...paths) => binding.posix.join(paths),
+  normalize: (path) => binding.posix.normalize(path),
+  parse: (path) => binding.posix.parse(path),
+  relative: (from, to) => binding.posix.relative(from, to),
+  resolve: (...paths) => binding.posix.resolve(paths),
+  cwd: () => binding.posix.cwd(),
+};
+
+// Only the methods ported in `nodejs_path::win32` are available.
+const win32 = {
+  sep: binding.win32.sep(),
+  delimiter: binding.win32.delimiter(),
+  isAbsolute: (path) => binding.win32.isAbsolute(path),
+};
+
+posix.posix = win32.posix = posix;
+posix.win32 = win32.win32 = win32;
+
+module.exports = { posix, win32 };
@@ -0,0 +1,147 @@
+//! # Description
+//!
+//! Node.js native addon exposing `nodejs_path` to JavaScript, so the Rust port can be dropped into an existing
+//! Node toolchain and compared with the built-in `path` module.
+//!
+//! The raw exports live under the `posix` and `win32` namespaces. Rust doesn't support variadic functions, so
+//! `join` and `resolve` take an array here; `index.js` wraps them back into the variadic signatures of Node.
+//!
+//! Only the methods ported in `nodejs_path::win32` are exported on `win32`.
+
+use napi_derive::napi;
+
+/// The JavaScript shape of [`nodejs_path::Parsed`], as returned by `path.parse()` and accepted by `path.format()`.
+#[napi(object)]
+pub struct ParsedPath {
+    pub root: String,
+    pub dir: String,
+    pub base: String,
+    pub ext: String,
+    pub name: String,
+}
+
+impl From<nodejs_path::Parsed> for ParsedPath {
+    fn from(parsed: nodejs_path::Parsed) -> Self {
+        Self {
+            root: parsed.root,
+            dir: parsed.dir,
+            base: parsed.base,
+            ext: parsed.ext,
+            name: parsed.name,
+        }
+    }
+}
+
+/// `path.format()` allows any of the fields to be omitted.
+#[napi(object)]
+pub struct FormatInput {
+    pub root: Option<String>,
+    pub dir: Option<String>,
+    pub base: Option<String>,
+    pub ext: Option<String>,
+    pub name: Option<String>,
+}
+
+impl From<FormatInput> for nodejs_path::Parsed {
+    fn from(input: FormatInput) -> Self {
+        Self {
+            root: input.root.unwrap_or_default(),
+            dir: input.dir.unwrap_or_default(),
+            base: input.base.unwrap_or_default(),
+            ext: input.ext.unwrap_or_default(),
+            name: input.name.unwrap_or_default(),
+        }
+    }
+}
+
+pub mod posix {
+    use napi_derive::napi;
+    use nodejs_path::posix;
+
+    use crate::{FormatInput, ParsedPath};
+
+    #[napi(namespace = "posix")]
+    pub fn sep() -> String {
+        posix::sep.to_string()
+    }
+
+    #[napi(namespace = "posix")]
+    pub fn delimiter() -> String {
+        posix::delimiter.to_string()
+    }
+
+    #[napi(namespace = "posix")]
+    pub fn basename(path: String, ext: Option<String>) -> String {
+        posix::basename_impl_without_ext(&path, ext.as_deref().unwrap_or(""))
+    }
+
+    #[napi(namespace = "posix")]
+    pub fn dirname(path: String) -> String {
+        posix::dirname(&path)
+    }
+
+    #[napi(namespace = "posix")]
+    pub fn extname(path: String) -> String {
+        posix::extname(&path)
+    }
+
+    #[napi(namespace = "posix")]
+    pub fn format(path_object: FormatInput) -> String {
+        posix::format(path_object.into())
+    }
+
+    #[napi(namespace = "posix")]
+    pub fn is_absolute(path: String) -> bool {
+        posix::is_absolute(&path)
+    }
+
+    #[napi(namespace = "posix")]
+    pub fn join(paths: Vec<String>) -> String {
+        posix::join_impl(&paths)
+    }
+
+    #[napi(namespace = "posix")]
+    pub fn normalize(path: String) -> String {
+        posix::normalize(&path)
+    }
+
+    #[napi(namespace = "posix")]
+    pub fn parse(path: String) -> ParsedPath {
+        posix::parse(&path).into()
+    }
+
+    #[napi(namespace = "posix")]
+    pub fn relative(from: String, to: String) -> String {
+        posix::relative(&from, &to)
+    }
+
+    #[napi(namespace = "posix")]
+    pub fn resolve(paths: Vec<String>) -> String {
+        posix::resolve_impl(&paths)
+    }
+
+    #[napi(namespace = "posix")]
+    pub fn cwd() -> String {
+        posix::cwd().to_owned()
+    }
+}
+
+pub mod win32 {
+    use napi_derive::napi;
+    use nodejs_path::win32;
+
+    #[napi(namespace = "win32")]
+    pub fn sep() -> String {
+        win32::sep.to_string()
+    }
+
+    #[napi(namespace = "win32")]
+    pub fn delimiter() -> String {
+        win32::delimiter.to_string()
+    }
+
+    #[napi(namespace = "win32")]
+    pub fn is_absolute(path: String) -> bool {
+        win32::is_absolute(&path)
+    }
+}
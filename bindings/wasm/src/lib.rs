@@ -0,0 +1,122 @@
+//! # Description
+//!
+//! WebAssembly build of `nodejs_path`, exposing `path` semantics to browsers through wasm-bindgen.
+//!
+//! `std::env::current_dir` does not exist on `wasm32-unknown-unknown`, so the working directory used by `resolve`
+//! and `relative` is kept here and can be changed with `setCwd`. It defaults to `/` on wasm and to the process cwd
+//! elsewhere.
+//!
+//! Rust doesn't support variadic functions, and neither do wasm-bindgen exports, so `join` and `resolve` take an
+//! array of segments. Only the methods ported in `nodejs_path::win32` are exported for win32.
+
+use std::cell::RefCell;
+
+use nodejs_path::{posix, win32, Parsed};
+use wasm_bindgen::prelude::*;
+
+thread_local! {
+    static CWD: RefCell<String> = RefCell::new(default_cwd());
+}
+
+fn default_cwd() -> String {
+    if cfg!(target_arch = "wasm32") {
+        "/".to_owned()
+    } else {
+        posix::cwd().to_owned()
+    }
+}
+
+/// Sets the working directory used to resolve relative paths, just like `process.chdir()`.
+#[wasm_bindgen(js_name = setCwd)]
+pub fn set_cwd(cwd: String) {
+    CWD.with(|current| *current.borrow_mut() = cwd);
+}
+
+/// Get the working directory used to resolve relative paths. Just like `process.cwd()`
+#[wasm_bindgen]
+pub fn cwd() -> String {
+    CWD.with(|cwd| cwd.borrow().clone())
+}
+
+/// The JavaScript shape of [`nodejs_path::Parsed`].
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ParsedPath {
+    pub root: String,
+    pub dir: String,
+    pub base: String,
+    pub ext: String,
+    pub name: String,
+}
+
+#[wasm_bindgen]
+impl ParsedPath {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl From<Parsed> for ParsedPath {
+    fn from(parsed: Parsed) -> Self {
+        Self {
+            root: parsed.root,
+            dir: parsed.dir,
+            base: parsed.base,
+            ext: parsed.ext,
+            name: parsed.name,
+        }
+    }
+}
+
+impl From<ParsedPath> for Parsed {
+    fn from(parsed: ParsedPath) -> Self {
+        Self {
+            root: parsed.root,
+            dir: parsed.dir,
+            base: parsed.base,
+            ext: parsed.ext,
+            name: parsed.name,
+        }
+    }
+}
+
+#[wasm_bindgen(js_name = posixJoin)]
+pub fn posix_join(paths: Vec<String>) -> String {
+    posix::join_impl(&paths)
+}
+
+#[wasm_bindgen(js_name = posixResolve)]
+pub fn posix_resolve(paths: Vec<String>) -> String {
+    CWD.with(|cwd| posix::resolve_impl_with_cwd(&paths, &cwd.borrow()))
+}
+
+#[wasm_bindgen(js_name = posixRelative)]
+pub fn posix_relative(from: &str, to: &str) -> String {
+    CWD.with(|cwd| posix::relative_with_cwd(from, to, &cwd.borrow()))
+}
+
+#[wasm_bindgen(js_name = posixParse)]
+pub fn posix_parse(path: &str) -> ParsedPath {
+    posix::parse(path).into()
+}
+
+#[wasm_bindgen(js_name = posixFormat)]
+pub fn posix_format(path_object: ParsedPath) -> String {
+    posix::format(path_object.into())
+}
+
+#[wasm_bindgen(js_name = posixNormalize)]
+pub fn posix_normalize(path: &str) -> String {
+    posix::normalize(path)
+}
+
+#[wasm_bindgen(js_name = posixIsAbsolute)]
+pub fn posix_is_absolute(path: &str) -> bool {
+    posix::is_absolute(path)
+}
+
+#[wasm_bindgen(js_name = win32IsAbsolute)]
+pub fn win32_is_absolute(path: &str) -> bool {
+    win32::is_absolute(path)
+}
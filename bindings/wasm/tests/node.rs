@@ -0,0 +1,70 @@
+//! Run in node with `cargo test --target wasm32-unknown-unknown` from `bindings/wasm` (needs `wasm-bindgen-cli`),
+//! or with `wasm-pack test --node`.
+#![cfg(target_arch = "wasm32")]
+
+use nodejs_path_wasm::*;
+use wasm_bindgen_test::wasm_bindgen_test;
+
+#[wasm_bindgen_test]
+fn join() {
+    assert_eq!(
+        posix_join(vec![
+            "/foo".to_owned(),
+            "bar".to_owned(),
+            "baz/asdf".to_owned(),
+            "quux".to_owned(),
+            "..".to_owned()
+        ]),
+        "/foo/bar/baz/asdf"
+    );
+    assert_eq!(posix_join(vec![]), ".");
+}
+
+#[wasm_bindgen_test]
+fn resolve_and_relative_use_configured_cwd() {
+    assert_eq!(cwd(), "/");
+    assert_eq!(posix_resolve(vec![]), "/");
+
+    set_cwd("/home/user".to_owned());
+    assert_eq!(
+        posix_resolve(vec!["a/b".to_owned(), "../c".to_owned()]),
+        "/home/user/a/c"
+    );
+    assert_eq!(
+        posix_resolve(vec![
+            "/var/lib".to_owned(),
+            "../".to_owned(),
+            "file/".to_owned()
+        ]),
+        "/var/file"
+    );
+    assert_eq!(posix_relative("a", "/home/other"), "../../other");
+    assert_eq!(
+        posix_relative("/data/orandea/test/aaa", "/data/orandea/impl/bbb"),
+        "../../impl/bbb"
+    );
+    set_cwd("/".to_owned());
+}
+
+#[wasm_bindgen_test]
+fn parse_and_format() {
+    let parsed = posix_parse("/home/user/dir/file.txt");
+    assert_eq!(parsed.root, "/");
+    assert_eq!(parsed.dir, "/home/user/dir");
+    assert_eq!(parsed.base, "file.txt");
+    assert_eq!(parsed.ext, ".txt");
+    assert_eq!(parsed.name, "file");
+    assert_eq!(posix_format(parsed), "/home/user/dir/file.txt");
+
+    let mut parsed = ParsedPath::new();
+    parsed.dir = "some/dir".to_owned();
+    assert_eq!(posix_format(parsed), "some/dir/");
+}
+
+#[wasm_bindgen_test]
+fn is_absolute() {
+    assert!(posix_is_absolute("/foo/bar"));
+    assert!(!posix_is_absolute("qux/"));
+    assert!(win32_is_absolute("C:\\foo\\.."));
+    assert!(!win32_is_absolute("C:cwd\\another"));
+}
@@ -0,0 +1,280 @@
+#!/usr/bin/env node
+// Regenerates the conformance data files from the vector tables of node's
+// test/parallel/test-path-parse-format.js, test-path-extname.js and test-path-basename.js.
+// Inputs are transcribed below, expected values come from the running node:
+//
+//   node conformance/generate.js
+//
+// To re-sync with a new node release, update the tables from its test files and run this again.
+'use strict';
+
+const fs = require('fs');
+const path = require('path');
+
+// test-path-parse-format.js
+
+const winPaths = [
+  // [path, root]
+  ['C:\\path\\dir\\index.html', 'C:\\'],
+  ['C:\\another_path\\DIR\\1\\2\\33\\\\index', 'C:\\'],
+  ['another_path\\DIR with spaces\\1\\2\\33\\index', ''],
+  ['\\', '\\'],
+  ['\\foo\\C:', '\\'],
+  ['file', ''],
+  ['file:stream', ''],
+  ['.\\file', ''],
+  ['C:', 'C:'],
+  ['C:.', 'C:'],
+  ['C:..', 'C:'],
+  ['C:abc', 'C:'],
+  ['C:\\', 'C:\\'],
+  ['C:\\abc', 'C:\\'],
+  ['', ''],
+
+  // unc
+  ['\\\\server\\share\\file_path', '\\\\server\\share\\'],
+  ['\\\\server two\\shared folder\\file path.zip', '\\\\server two\\shared folder\\'],
+  ['\\\\teela\\admin$\\system32', '\\\\teela\\admin$\\'],
+  ['\\\\?\\UNC\\server\\share', '\\\\?\\UNC\\'],
+];
+
+const winSpecialCaseParseTests = [
+  't',
+  '/foo/bar',
+];
+
+const winSpecialCaseFormatTests = [
+  { dir: 'some\\dir' },
+  { base: 'index.html' },
+  { root: 'C:\\' },
+  { name: 'index', ext: '.html' },
+  { dir: 'some\\dir', name: 'index', ext: '.html' },
+  { root: 'C:\\', name: 'index', ext: '.html' },
+  { name: 'x', ext: 'png' },
+  {},
+];
+
+const unixPaths = [
+  // [path, root]
+  ['/home/user/dir/file.txt', '/'],
+  ['/home/user/a dir/another File.zip', '/'],
+  ['/home/user/a dir//another&File.', '/'],
+  ['/home/user/a$$$dir//another File.zip', '/'],
+  ['user/dir/another File.zip', ''],
+  ['file', ''],
+  ['.\\file', ''],
+  ['./file', ''],
+  ['C:\\foo', ''],
+  ['/', '/'],
+  ['', ''],
+  ['.', ''],
+  ['..', ''],
+  ['/foo', '/'],
+  ['/foo.', '/'],
+  ['/foo.bar', '/'],
+  ['/.', '/'],
+  ['/.foo', '/'],
+  ['/.foo.bar', '/'],
+  ['/foo/bar.baz', '/'],
+];
+
+// Not in node's tables, but reported against this crate.
+const unixSpecialCaseParseTests = [
+  '/foo/',
+  '..',
+  '.bashrc',
+  '.bashrc.',
+  '/..',
+  '//..',
+  '../..',
+];
+
+const unixSpecialCaseFormatTests = [
+  { dir: 'some/dir' },
+  { base: 'index.html' },
+  { root: '/' },
+  { name: 'index', ext: '.html' },
+  { dir: 'some/dir', name: 'index', ext: '.html' },
+  { root: '/', name: 'index', ext: '.html' },
+  { name: 'x', ext: 'png' },
+  { name: 'x', ext: '.png' },
+  {},
+];
+
+const trailingTests = {
+  win32: ['.\\', '\\\\', 'c:\\foo\\\\\\', 'D:\\foo\\\\\\bar.baz'],
+  posix: ['./', '//', '///', '/foo///', '/foo///bar.baz'],
+};
+
+// test-path-extname.js
+
+const extnamePaths = [
+  ['', ''],
+  ['/path/to/file', ''],
+  ['/path/to/file.ext', '.ext'],
+  ['/path.to/file.ext', '.ext'],
+  ['/path.to/file', ''],
+  ['/path.to/.file', ''],
+  ['/path.to/.file.ext', '.ext'],
+  ['/path/to/f.ext', '.ext'],
+  ['/path/to/..ext', '.ext'],
+  ['/path/to/..', ''],
+  ['file', ''],
+  ['file.ext', '.ext'],
+  ['.file', ''],
+  ['.file.ext', '.ext'],
+  ['/file', ''],
+  ['/file.ext', '.ext'],
+  ['/.file', ''],
+  ['/.file.ext', '.ext'],
+  ['.path/file.ext', '.ext'],
+  ['file.ext.ext', '.ext'],
+  ['file.', '.'],
+  ['.', ''],
+  ['./', ''],
+  ['.file.ext', '.ext'],
+  ['.file', ''],
+  ['.file.', '.'],
+  ['.file..', '.'],
+  ['..', ''],
+  ['../', ''],
+  ['..file.ext', '.ext'],
+  ['..file', '.file'],
+  ['..file.', '.'],
+  ['..file..', '.'],
+  ['...', '.'],
+  ['...ext', '.ext'],
+  ['....', '.'],
+  ['file.ext/', '.ext'],
+  ['file.ext//', '.ext'],
+  ['file/', ''],
+  ['file//', ''],
+  ['file./', '.'],
+  ['file.//', '.'],
+].map(([input]) => input);
+
+const extnameWin32Only = ['.\\', '..\\', 'file.ext\\', 'file.ext\\\\', 'file\\', 'file\\\\', 'file.\\', 'file.\\\\'];
+// On *nix, backslash is a valid name component like any other character.
+const extnamePosixOnly = extnameWin32Only;
+// Not in node's tables, but reported against this crate.
+const extnameReported = ['/..', '//..', '/...', 'a/..', '.../..'];
+
+// test-path-basename.js
+
+const basenameTests = [
+  ['.js', '.js'],
+  ['js', '.js'],
+  ['file.js', '.ts'],
+  ['file', '.js'],
+  ['file.js.old', '.js.old'],
+  [''],
+  ['/dir/basename.ext'],
+  ['/basename.ext'],
+  ['basename.ext'],
+  ['basename.ext/'],
+  ['basename.ext//'],
+  ['aaa/bbb', '/bbb'],
+  ['aaa/bbb', 'a/bbb'],
+  ['aaa/bbb', 'bbb'],
+  ['aaa/bbb//', 'bbb'],
+  ['aaa/bbb', 'bb'],
+  ['aaa/bbb', 'b'],
+  ['/aaa/bbb', '/bbb'],
+  ['/aaa/bbb', 'a/bbb'],
+  ['/aaa/bbb', 'bbb'],
+  ['/aaa/bbb//', 'bbb'],
+  ['/aaa/bbb', 'bb'],
+  ['/aaa/bbb', 'b'],
+  ['/aaa/bbb'],
+  ['/aaa/'],
+  ['/aaa/b'],
+  ['/a/b'],
+  ['//a'],
+  ['a', 'a'],
+];
+
+// On Windows a backslash acts as a path separator.
+const basenameWin32Only = [
+  ['\\dir\\basename.ext'],
+  ['\\basename.ext'],
+  ['basename.ext'],
+  ['basename.ext\\'],
+  ['basename.ext\\\\'],
+  ['foo'],
+  ['aaa\\bbb', '\\bbb'],
+  ['aaa\\bbb', 'a\\bbb'],
+  ['aaa\\bbb', 'bbb'],
+  ['aaa\\bbb\\\\\\\\', 'bbb'],
+  ['aaa\\bbb', 'bb'],
+  ['aaa\\bbb', 'b'],
+  ['C:'],
+  ['C:.'],
+  ['C:\\'],
+  ['C:\\dir\\base.ext'],
+  ['C:\\basename.ext'],
+  ['C:basename.ext'],
+  ['C:basename.ext\\'],
+  ['C:basename.ext\\\\'],
+  ['C:foo'],
+  ['file:stream'],
+  ['a', 'a'],
+];
+
+// On unix a backslash is just treated as any other character.
+const basenamePosixOnly = [
+  ['\\dir\\basename.ext'],
+  ['\\basename.ext'],
+  ['basename.ext'],
+  ['basename.ext\\'],
+  ['basename.ext\\\\'],
+  ['foo'],
+  // POSIX filenames may include control characters
+  // c.f. http://www.dwheeler.com/essays/fixing-unix-linux-filenames.html
+  [`/a/b/Icon${String.fromCharCode(13)}`],
+];
+
+// Not in node's tables, but reported against this crate: `ext` is compared by UTF-16 length.
+const basenameReported = [
+  ['//', '.🦀'],
+  ['🦀.rs', '.rs'],
+  ['a🦀', '🦀'],
+  ['/a/b🦀', 'x🦀'],
+];
+
+function parseFormat(p, paths, specialCaseParse, specialCaseFormat, trailing) {
+  return {
+    parseFormat: paths.map(([input, root]) => ({ input, root, parsed: p.parse(input) })),
+    parse: [...specialCaseParse, ...trailing].map((input) => ({ input, expected: p.parse(input) })),
+    format: specialCaseFormat.map((input) => ({ input, expected: p.format(input) })),
+  };
+}
+
+function calls(fn, inputs) {
+  return inputs.map((args) => {
+    args = Array.isArray(args) ? args : [args];
+    return { args, expected: fn(...args) };
+  });
+}
+
+function write(name, data) {
+  const file = path.join(__dirname, name);
+  fs.writeFileSync(file, `${JSON.stringify({ node: process.version, ...data }, null, 2)}\n`);
+  console.log(`wrote ${path.relative(process.cwd(), file)}`);
+}
+
+write('parse-format.json', {
+  posix: parseFormat(path.posix, unixPaths, unixSpecialCaseParseTests, unixSpecialCaseFormatTests,
+                     trailingTests.posix),
+  win32: parseFormat(path.win32, winPaths, winSpecialCaseParseTests, winSpecialCaseFormatTests,
+                     trailingTests.win32),
+});
+
+write('extname.json', {
+  posix: calls(path.posix.extname, [...extnamePaths, ...extnamePosixOnly, ...extnameReported]),
+  win32: calls(path.win32.extname, [...extnamePaths.map((p) => p.replace(/\//g, '\\')), ...extnameWin32Only]),
+});
+
+write('basename.json', {
+  posix: calls(path.posix.basename, [...basenameTests, ...basenamePosixOnly, ...basenameReported]),
+  win32: calls(path.win32.basename, [...basenameTests, ...basenameWin32Only]),
+});
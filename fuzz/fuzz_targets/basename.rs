@@ -0,0 +1,20 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nodejs_path::posix;
+
+// `path` and `ext` are separated by the first NUL byte.
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let (path, ext) = input.split_once('\0').unwrap_or((input, ""));
+
+    // A partially matched `ext` keeps the trailing separators, e.g. `basename("a/", "xa") == "a/"`.
+    let basename = posix::basename_impl_without_ext(path, ext);
+    assert!(path.contains(&basename));
+
+    let full = posix::basename_impl(path);
+    assert!(path.contains(&full));
+    assert!(!full.contains('/'));
+});
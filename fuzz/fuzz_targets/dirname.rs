@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nodejs_path::posix;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(path) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let dirname = posix::dirname(path);
+    assert!(!dirname.is_empty());
+    assert!(dirname == "." || path.starts_with(&dirname));
+    assert_eq!(posix::is_absolute(&dirname), posix::is_absolute(path));
+});
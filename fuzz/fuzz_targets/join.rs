@@ -0,0 +1,25 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nodejs_path::posix;
+
+// Segments are separated by NUL bytes.
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let segments = input.split('\0').collect::<Vec<_>>();
+
+    let joined = posix::join_impl(&segments);
+    assert!(!joined.is_empty());
+    assert_eq!(posix::normalize(&joined), joined);
+
+    let non_empty = segments
+        .iter()
+        .filter(|s| !s.is_empty())
+        .copied()
+        .collect::<Vec<_>>();
+    if !non_empty.is_empty() {
+        assert_eq!(joined, posix::normalize(&non_empty.join("/")));
+    }
+});
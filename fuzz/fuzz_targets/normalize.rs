@@ -0,0 +1,15 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nodejs_path::posix;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(path) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let normalized = posix::normalize(path);
+    assert!(!normalized.is_empty());
+    assert_eq!(posix::is_absolute(&normalized), posix::is_absolute(path));
+    assert_eq!(posix::normalize(&normalized), normalized);
+});
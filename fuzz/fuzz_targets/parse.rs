@@ -0,0 +1,26 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nodejs_path::posix;
+
+fuzz_target!(|data: &[u8]| {
+    let Ok(path) = std::str::from_utf8(data) else {
+        return;
+    };
+
+    let parsed = posix::parse(path);
+    assert!(parsed.dir.starts_with(&parsed.root));
+    assert_eq!(parsed.base, posix::basename_impl(path));
+    assert_eq!(parsed.base, format!("{}{}", parsed.name, parsed.ext));
+
+    // `parse` collapses a leading run of separators into the root and drops trailing ones, `dirname` keeps `//`.
+    if !path.starts_with("//") {
+        if !parsed.dir.is_empty() {
+            assert_eq!(parsed.dir, posix::dirname(path));
+        }
+        assert_eq!(posix::parse(&posix::format(parsed.clone())), parsed);
+        if !path.ends_with('/') {
+            assert_eq!(posix::format(parsed), path);
+        }
+    }
+});
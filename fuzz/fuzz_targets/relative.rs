@@ -0,0 +1,21 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nodejs_path::posix;
+
+// `from` and `to` are separated by the first NUL byte.
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let (from, to) = input.split_once('\0').unwrap_or((input, ""));
+
+    let relative = posix::relative_with_cwd(from, to, "/cwd");
+    assert!(!posix::is_absolute(&relative));
+    assert_eq!(
+        posix::resolve_impl_with_cwd(&[from, &relative], "/cwd"),
+        posix::resolve_impl_with_cwd(&[to], "/cwd")
+    );
+
+    posix::relative(from, to);
+});
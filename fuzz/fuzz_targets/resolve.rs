@@ -0,0 +1,22 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use nodejs_path::posix;
+
+// Segments are separated by NUL bytes.
+fuzz_target!(|data: &[u8]| {
+    let Ok(input) = std::str::from_utf8(data) else {
+        return;
+    };
+    let segments = input.split('\0').collect::<Vec<_>>();
+
+    let resolved = posix::resolve_impl_with_cwd(&segments, "/cwd");
+    assert!(posix::is_absolute(&resolved));
+    assert_eq!(posix::normalize(&resolved), resolved);
+    assert_eq!(
+        posix::resolve_impl_with_cwd(&[&resolved], "/other"),
+        resolved
+    );
+
+    posix::resolve_impl(&segments);
+});
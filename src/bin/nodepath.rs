@@ -0,0 +1,273 @@
+//! `nodepath` checks what `path.*` would return without starting Node.
+//!
+//! ```plain
+//! nodepath [--posix | --win32] [--cwd <dir>] <command> [args...]
+//! nodepath [--posix | --win32] [--cwd <dir>] --batch
+//! ```
+//!
+//! In batch mode, every line of stdin is a request whose tab-separated fields are read as the arguments above,
+//! and one line is printed per request. `parse` prints JSON there, and a failed request prints a NUL byte followed
+//! by `error: <message>`, which no result can start with.
+
+use std::{
+    io::{self, BufRead, Write},
+    process,
+};
+
+use nodejs_path::{posix, win32, Parsed};
+
+const USAGE: &str = "\
+Usage: nodepath [--posix | --win32] [--cwd <dir>] <command> [args...]
+       nodepath [--posix | --win32] [--cwd <dir>] --batch
+
+Commands:
+  join [paths...]          path.join()
+  resolve [paths...]       path.resolve()
+  relative <from> <to>     path.relative()
+  normalize <path>         path.normalize()
+  parse [--json] <path>    path.parse()
+  format [--root <root>] [--dir <dir>] [--base <base>] [--name <name>] [--ext <ext>]
+                           path.format()
+  dirname <path>           path.dirname()
+  basename <path> [ext]    path.basename()
+  extname <path>           path.extname()
+  is-absolute <path>       path.isAbsolute()
+
+Options:
+  --posix                  Use POSIX semantics (default)
+  --win32                  Use Windows semantics
+  --cwd <dir>              Resolve relative paths against <dir> instead of the current directory
+  --batch                  Read tab-separated requests from stdin and print one line per request.
+                           parse prints JSON, and errors print a NUL byte followed by `error: <message>`
+  -h, --help               Print this help";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flavor {
+    Posix,
+    Win32,
+}
+
+#[derive(Debug, Clone)]
+struct Options {
+    flavor: Flavor,
+    cwd: Option<String>,
+    // Every output is a single line.
+    batch: bool,
+}
+
+#[derive(Debug)]
+enum Error {
+    Usage(String),
+    Unsupported(&'static str),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Usage(msg) => write!(f, "{}", msg),
+            Error::Unsupported(command) => write!(f, "win32 {} is not implemented yet", command),
+        }
+    }
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let defaults = Options {
+        flavor: Flavor::Posix,
+        cwd: None,
+        batch: false,
+    };
+
+    let (options, rest) = match parse_options(&defaults, &args) {
+        Ok(parsed) => parsed,
+        Err(err) => exit_with(&err),
+    };
+
+    match rest.first().map(String::as_str) {
+        Some("-h") | Some("--help") => println!("{}", USAGE),
+        Some("--batch") if rest.len() == 1 => {
+            if let Err(err) = batch(&options) {
+                eprintln!("nodepath: {}", err);
+                process::exit(1);
+            }
+        }
+        _ => match run(&options, rest) {
+            Ok(output) => println!("{}", output),
+            Err(err) => exit_with(&err),
+        },
+    }
+}
+
+fn exit_with(err: &Error) -> ! {
+    eprintln!("nodepath: {}", err);
+    if let Error::Usage(_) = err {
+        eprintln!("\n{}", USAGE);
+        process::exit(2);
+    }
+    process::exit(1);
+}
+
+fn batch(options: &Options) -> io::Result<()> {
+    let options = &Options {
+        batch: true,
+        ..options.clone()
+    };
+    let stdin = io::stdin();
+    let stdout = io::stdout();
+    let mut stdout = stdout.lock();
+    for line in stdin.lock().lines() {
+        let line = line?;
+        let args = line.split('\t').map(str::to_owned).collect::<Vec<_>>();
+        let output = parse_options(options, &args).and_then(|(options, rest)| run(&options, rest));
+        match output {
+            Ok(output) => writeln!(stdout, "{}", output)?,
+            Err(err) => writeln!(stdout, "\0error: {}", err)?,
+        }
+    }
+    Ok(())
+}
+
+/// Consumes the leading flavor and cwd flags.
+fn parse_options<'a>(
+    defaults: &Options,
+    mut args: &'a [String],
+) -> Result<(Options, &'a [String]), Error> {
+    let mut options = defaults.clone();
+    while let Some(arg) = args.first() {
+        match arg.as_str() {
+            "--posix" => options.flavor = Flavor::Posix,
+            "--win32" => options.flavor = Flavor::Win32,
+            "--cwd" => {
+                let cwd = args
+                    .get(1)
+                    .ok_or_else(|| Error::Usage("--cwd requires a value".to_owned()))?;
+                options.cwd = Some(cwd.clone());
+                args = &args[1..];
+            }
+            _ => break,
+        }
+        args = &args[1..];
+    }
+    Ok((options, args))
+}
+
+fn run(options: &Options, args: &[String]) -> Result<String, Error> {
+    let (command, args) = args
+        .split_first()
+        .ok_or_else(|| Error::Usage("missing command".to_owned()))?;
+    let args = args.iter().map(String::as_str).collect::<Vec<_>>();
+
+    if options.flavor == Flavor::Win32 {
+        return match command.as_str() {
+            "is-absolute" => Ok(win32::is_absolute(exactly_one(command, &args)?).to_string()),
+            "join" => Err(Error::Unsupported("join")),
+            "resolve" => Err(Error::Unsupported("resolve")),
+            "relative" => Err(Error::Unsupported("relative")),
+            "normalize" => Err(Error::Unsupported("normalize")),
+            "parse" => Err(Error::Unsupported("parse")),
+            "format" => Err(Error::Unsupported("format")),
+            "dirname" => Ok(win32::dirname(exactly_one(command, &args)?)),
+            "basename" => Err(Error::Unsupported("basename")),
+            "extname" => Err(Error::Unsupported("extname")),
+            other => Err(Error::Usage(format!("unknown command `{}`", other))),
+        };
+    }
+
+    // A relative `--cwd` is itself relative to the process cwd, so `resolve` always returns an absolute path.
+    let cwd = match &options.cwd {
+        Some(cwd) => posix::resolve_impl(&[cwd]),
+        None => posix::cwd().to_owned(),
+    };
+    let cwd = cwd.as_str();
+    match command.as_str() {
+        "join" => Ok(posix::join_impl(&args)),
+        "resolve" => Ok(posix::resolve_impl_with_cwd(&args, cwd)),
+        "relative" => match args.as_slice() {
+            [from, to] => Ok(posix::relative_with_cwd(from, to, cwd)),
+            _ => Err(Error::Usage("relative expects <from> <to>".to_owned())),
+        },
+        "normalize" => Ok(posix::normalize(exactly_one(command, &args)?)),
+        "parse" => match args.as_slice() {
+            ["--json", path] => Ok(parsed_to_json(&posix::parse(path))),
+            [path] if options.batch => Ok(parsed_to_json(&posix::parse(path))),
+            [path] => Ok(parsed_to_lines(&posix::parse(path))),
+            _ => Err(Error::Usage("parse expects [--json] <path>".to_owned())),
+        },
+        "format" => Ok(posix::format(parse_format_args(&args)?)),
+        "dirname" => Ok(posix::dirname(exactly_one(command, &args)?)),
+        "basename" => match args.as_slice() {
+            [path] => Ok(posix::basename_impl(path)),
+            [path, ext] => Ok(posix::basename_impl_without_ext(path, ext)),
+            _ => Err(Error::Usage("basename expects <path> [ext]".to_owned())),
+        },
+        "extname" => Ok(posix::extname(exactly_one(command, &args)?)),
+        "is-absolute" => Ok(posix::is_absolute(exactly_one(command, &args)?).to_string()),
+        other => Err(Error::Usage(format!("unknown command `{}`", other))),
+    }
+}
+
+fn exactly_one<'a>(command: &str, args: &[&'a str]) -> Result<&'a str, Error> {
+    match args {
+        [path] => Ok(path),
+        _ => Err(Error::Usage(format!("{} expects <path>", command))),
+    }
+}
+
+fn parse_format_args(mut args: &[&str]) -> Result<Parsed, Error> {
+    let mut parsed = Parsed::default();
+    while let [flag, value, rest @ ..] = args {
+        let field = match *flag {
+            "--root" => &mut parsed.root,
+            "--dir" => &mut parsed.dir,
+            "--base" => &mut parsed.base,
+            "--name" => &mut parsed.name,
+            "--ext" => &mut parsed.ext,
+            other => return Err(Error::Usage(format!("unknown format field `{}`", other))),
+        };
+        *field = value.to_string();
+        args = rest;
+    }
+    match args {
+        [] => Ok(parsed),
+        [flag, ..] => Err(Error::Usage(format!("{} requires a value", flag))),
+    }
+}
+
+fn parsed_to_lines(parsed: &Parsed) -> String {
+    format!(
+        "root: {}\ndir: {}\nbase: {}\next: {}\nname: {}",
+        parsed.root, parsed.dir, parsed.base, parsed.ext, parsed.name
+    )
+}
+
+/// Same key order as `JSON.stringify(path.parse(p))`.
+fn parsed_to_json(parsed: &Parsed) -> String {
+    format!(
+        "{{\"root\":{},\"dir\":{},\"base\":{},\"ext\":{},\"name\":{}}}",
+        json_string(&parsed.root),
+        json_string(&parsed.dir),
+        json_string(&parsed.base),
+        json_string(&parsed.ext),
+        json_string(&parsed.name)
+    )
+}
+
+fn json_string(s: &str) -> String {
+    let mut out = String::with_capacity(s.len() + 2);
+    out.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            '\u{08}' => out.push_str("\\b"),
+            '\u{0c}' => out.push_str("\\f"),
+            c if (c as u32) < 0x20 => out.push_str(&format!("\\u{:04x}", c as u32)),
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+    out
+}
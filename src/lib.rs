@@ -39,13 +39,29 @@
 //! - path.resolve           => [`nodejs_path::resolve!`](self::resolve)
 //! - path.sep               => [`nodejs_path::sep`](self::sep)
 //! - path.toNamespacedPath  => [`nodejs_path::to_namespaced_path`](self::to_namespaced_path)
+//!
+//! # `no_std`
+//! The lexical algorithms only need `alloc`. Disable the default `std` feature to build without std, in which case
+//! [`posix::cwd`], [`posix::relative`] and [`posix::resolve!`](crate::posix::resolve) are unavailable and
+//! [`posix::resolve_impl_with_cwd`] and [`posix::relative_with_cwd`] take an explicit cwd instead.
+//!
+//! # Module resolution
+//! The default `resolver` feature adds [`resolver`], which resolves import specifiers to files the way Node does,
+//! on posix paths and through a pluggable filesystem.
 
 // Align to https://nodejs.org/docs/latest-v16.x/api/path.html
 
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
 #[cfg(test)]
 mod tests;
 
 mod path;
+/// `nodejs_path::resolver` resolves module specifiers like Node's loaders do.
+#[cfg(feature = "resolver")]
+pub mod resolver;
 
 #[cfg(target_family = "unix")]
 pub use path::posix::*;
@@ -55,4 +71,10 @@ pub use path::win32::*;
 pub use path::posix;
 pub use path::win32;
 
+pub use path::components::{Component, Components};
+pub use path::convert::{to_posix, to_win32, DriveMapping};
+#[cfg(feature = "std")]
+pub use path::find_up::{find_up, FindUpOptions};
+#[cfg(feature = "std")]
+pub use path::interner::{PathId, PathInterner};
 pub use path::shared::Parsed;
@@ -0,0 +1,268 @@
+use alloc::collections::VecDeque;
+use core::iter::FusedIterator;
+
+use super::shared::{is_path_separator, is_posix_path_separator};
+
+/// A single component of a path, as yielded by [`posix::components`](crate::posix::components) and
+/// [`win32::components`](crate::win32::components).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Component<'a> {
+    /// The root of an absolute path. `/` on POSIX, and a drive (`C:\`, or `C:` for drive-relative paths), a UNC
+    /// root (`\\server\share\`) or a single separator on Windows.
+    Root(&'a str),
+    /// `.`
+    CurDir,
+    /// `..`
+    ParentDir,
+    /// Any other segment.
+    Normal(&'a str),
+}
+
+impl<'a> Component<'a> {
+    /// The component as it appears in the path.
+    /// ```rust
+    /// use nodejs_path::Component;
+    /// assert_eq!(Component::ParentDir.as_str(), "..");
+    /// assert_eq!(Component::Normal("foo").as_str(), "foo");
+    /// ```
+    pub fn as_str(&self) -> &'a str {
+        match self {
+            Component::Root(root) => root,
+            Component::CurDir => ".",
+            Component::ParentDir => "..",
+            Component::Normal(segment) => segment,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Flavor {
+    Posix,
+    Win32,
+}
+
+impl Flavor {
+    #[inline]
+    fn is_separator(self, byte: u8) -> bool {
+        let code = byte as char;
+        match self {
+            Flavor::Posix => is_posix_path_separator(&code),
+            Flavor::Win32 => is_path_separator(&code),
+        }
+    }
+}
+
+/// An iterator over the [`Component`]s of a path, borrowing from it instead of allocating.
+///
+/// Repeated separators are skipped, and so is a trailing one. With [`Components::lexical`], `.` segments are
+/// dropped and `..` segments cancel the segment before them, like [`posix::normalize`](crate::posix::normalize).
+///
+/// ```rust
+/// use nodejs_path::{posix, Component};
+///
+/// let mut components = posix::components("/foo//bar/../baz.txt");
+/// assert_eq!(components.next(), Some(Component::Root("/")));
+/// assert_eq!(components.next_back(), Some(Component::Normal("baz.txt")));
+/// assert_eq!(components.as_str(), "foo//bar/..");
+///
+/// assert!(posix::components("/project/node_modules/react/index.js")
+///     .any(|c| c == Component::Normal("node_modules")));
+///
+/// let lexical = posix::components("a/./b/../../../c").lexical().collect::<Vec<_>>();
+/// assert_eq!(lexical, [Component::ParentDir, Component::Normal("c")]);
+/// ```
+///
+/// Being double-ended, it is enough for `basename` and `dirname` without allocating:
+/// ```rust
+/// use nodejs_path::{posix, Component};
+///
+/// fn basename(path: &str) -> &str {
+///     match posix::components(path).next_back() {
+///         Some(Component::Root(_)) | None => "",
+///         Some(component) => component.as_str(),
+///     }
+/// }
+///
+/// fn dirname(path: &str) -> &str {
+///     let mut components = posix::components(path);
+///     match components.next_back() {
+///         Some(Component::Root(root)) => root,
+///         None => ".",
+///         Some(_) => match components.as_str() {
+///             "" => ".",
+///             dir => dir,
+///         },
+///     }
+/// }
+///
+/// assert_eq!(basename("/foo/bar/baz.txt/"), posix::basename_impl("/foo/bar/baz.txt/"));
+/// assert_eq!(dirname("/foo/bar/baz.txt"), posix::dirname("/foo/bar/baz.txt"));
+/// assert_eq!(dirname("baz.txt"), posix::dirname("baz.txt"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct Components<'a> {
+    path: &'a str,
+    flavor: Flavor,
+    root_len: usize,
+    // Whether the root is absolute, unlike the drive-relative win32 `C:`.
+    absolute: bool,
+    root_pending: bool,
+    // Byte ranges of the segments that survive lexical normalization, in order, once `lexical` is called.
+    kept: Option<VecDeque<(usize, usize)>>,
+    // Byte range of `path` that is left to iterate, excluding the root.
+    front: usize,
+    back: usize,
+}
+
+impl<'a> Components<'a> {
+    pub(crate) fn new(path: &'a str, flavor: Flavor, root_len: usize, absolute: bool) -> Self {
+        Self {
+            path,
+            flavor,
+            root_len,
+            absolute,
+            root_pending: root_len > 0,
+            kept: None,
+            front: root_len,
+            back: path.len(),
+        }
+    }
+
+    /// Drop `.` segments and let `..` cancel the segment before it. `..` segments that climb above the root of an
+    /// absolute path are dropped too, while a drive-relative root like `C:` keeps them.
+    ///
+    /// Unlike the rest of the iterator, this allocates: whether a segment survives depends on the segments after
+    /// it, so the survivors are found here in one pass and their positions kept.
+    pub fn lexical(mut self) -> Self {
+        // A stack of the segments kept so far, which a `..` pops, makes this linear in the length of the path.
+        let mut kept = VecDeque::new();
+        let mut normals = 0usize;
+        let mut from = self.root_len;
+        while let Some((start, end)) = self.segment_after(from, self.path.len()) {
+            match &self.path[start..end] {
+                "." => {}
+                ".." if normals > 0 => {
+                    kept.pop_back();
+                    normals -= 1;
+                }
+                // Nothing to climb above the root.
+                ".." if self.absolute => {}
+                ".." => kept.push_back((start, end)),
+                _ => {
+                    kept.push_back((start, end));
+                    normals += 1;
+                }
+            }
+            from = end;
+        }
+        // Leave out what has been iterated already.
+        kept.retain(|&(start, end)| start >= self.front && end <= self.back);
+        self.kept = Some(kept);
+        self
+    }
+
+    /// The part of the path that is left to iterate, without trailing separators unless they form the root.
+    /// ```rust
+    /// let mut components = nodejs_path::posix::components("/foo/bar/");
+    /// components.next_back();
+    /// assert_eq!(components.as_str(), "/foo");
+    /// components.next_back();
+    /// assert_eq!(components.as_str(), "/");
+    /// ```
+    pub fn as_str(&self) -> &'a str {
+        let start = if self.root_pending { 0 } else { self.front };
+        let mut end = self.back.max(start);
+        while end > start.max(self.root_len)
+            && self.flavor.is_separator(self.path.as_bytes()[end - 1])
+        {
+            end -= 1;
+        }
+        &self.path[start..end]
+    }
+
+    fn classify(&self, segment: &'a str) -> Component<'a> {
+        match segment {
+            "." => Component::CurDir,
+            ".." => Component::ParentDir,
+            segment => Component::Normal(segment),
+        }
+    }
+
+    /// The segment starting at or after `from`, as a byte range.
+    fn segment_after(&self, mut from: usize, until: usize) -> Option<(usize, usize)> {
+        let bytes = self.path.as_bytes();
+        while from < until && self.flavor.is_separator(bytes[from]) {
+            from += 1;
+        }
+        if from >= until {
+            return None;
+        }
+        let mut end = from;
+        while end < until && !self.flavor.is_separator(bytes[end]) {
+            end += 1;
+        }
+        Some((from, end))
+    }
+
+    /// The segment ending at or before `until`, as a byte range.
+    fn segment_before(&self, from: usize, mut until: usize) -> Option<(usize, usize)> {
+        let bytes = self.path.as_bytes();
+        while until > from && self.flavor.is_separator(bytes[until - 1]) {
+            until -= 1;
+        }
+        if until <= from {
+            return None;
+        }
+        let mut start = until;
+        while start > from && !self.flavor.is_separator(bytes[start - 1]) {
+            start -= 1;
+        }
+        Some((start, until))
+    }
+}
+
+impl<'a> Iterator for Components<'a> {
+    type Item = Component<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.root_pending {
+            self.root_pending = false;
+            return Some(Component::Root(&self.path[..self.root_len]));
+        }
+        let next = match &mut self.kept {
+            Some(kept) => kept.pop_front(),
+            None => self.segment_after(self.front, self.back),
+        };
+        match next {
+            Some((start, end)) => {
+                self.front = end;
+                Some(self.classify(&self.path[start..end]))
+            }
+            None => {
+                self.front = self.back;
+                None
+            }
+        }
+    }
+}
+
+impl<'a> DoubleEndedIterator for Components<'a> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        let next = match &mut self.kept {
+            Some(kept) => kept.pop_back(),
+            None => self.segment_before(self.front, self.back),
+        };
+        if let Some((start, end)) = next {
+            self.back = start;
+            return Some(self.classify(&self.path[start..end]));
+        }
+        self.back = self.front;
+        if self.root_pending {
+            self.root_pending = false;
+            return Some(Component::Root(&self.path[..self.root_len]));
+        }
+        None
+    }
+}
+
+impl<'a> FusedIterator for Components<'a> {}
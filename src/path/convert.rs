@@ -0,0 +1,146 @@
+use alloc::{borrow::ToOwned, format, string::String};
+
+use super::{
+    posix,
+    win32::{self, Prefix},
+};
+
+/// Where drive letters live in the posix tree, for [`to_posix`] and [`to_win32`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DriveMapping<'a> {
+    /// `C:\x` is `/c/x`, like MSYS2 and Git Bash.
+    Msys,
+    /// `C:\x` is `/mnt/c/x`, like WSL.
+    Wsl,
+    /// `C:\x` is `{prefix}c/x`. The prefix should be an absolute posix directory ending with `/`, e.g. `/cygdrive/`.
+    Prefix(&'a str),
+}
+
+impl<'a> DriveMapping<'a> {
+    fn prefix(&self) -> &'a str {
+        match self {
+            DriveMapping::Msys => "/",
+            DriveMapping::Wsl => "/mnt/",
+            DriveMapping::Prefix(prefix) => prefix,
+        }
+    }
+}
+
+/// Converts a win32 path to its canonical posix form.
+///
+/// - Drives are mapped with `drives` and their letter is lowercased. `C:\` is `/c`.
+/// - UNC roots become `//server/share`, the one place where a leading `//` is kept.
+/// - Verbatim `\\?\C:\` and `\\?\UNC\` paths are mapped like their plain counterparts.
+/// - A root without a drive (`\x`) becomes `/x`. Relative paths stay relative.
+///
+/// The rest of the path is passed through [`posix::normalize`]. Drive-relative (`C:x`), device namespace
+/// (`\\.\pipe\x`) and other verbatim paths have no posix equivalent and return `None`, and so do rooted paths
+/// that `drives` would read back as a drive, like `\c\x` with [`DriveMapping::Msys`].
+/// ```rust
+/// use nodejs_path::{to_posix, DriveMapping};
+///
+/// assert_eq!(to_posix("C:\\Users\\me\\..\\x", DriveMapping::Msys).unwrap(), "/c/Users/x");
+/// assert_eq!(to_posix("C:/Users", DriveMapping::Wsl).unwrap(), "/mnt/c/Users");
+/// assert_eq!(to_posix("\\\\srv\\share\\a", DriveMapping::Msys).unwrap(), "//srv/share/a");
+/// assert_eq!(to_posix("a\\.\\b\\", DriveMapping::Msys).unwrap(), "a/b/");
+/// assert_eq!(to_posix("C:foo", DriveMapping::Msys), None);
+/// ```
+pub fn to_posix(path: &str, drives: DriveMapping) -> Option<String> {
+    let rest = &path[win32::root_len(path)..];
+    let root = match win32::prefix(path) {
+        None => return Some(posix::normalize(&rest.replace(win32::sep, "/"))),
+        Some(Prefix::Drive(letter)) => drive_root(letter, drives),
+        Some(Prefix::Verbatim(disk)) => match disk.as_bytes() {
+            [letter, b':'] if letter.is_ascii_alphabetic() => drive_root(*letter as char, drives),
+            _ => return None,
+        },
+        Some(Prefix::Unc { server, share }) => format!("//{}/{}", server, share),
+        Some(Prefix::VerbatimUnc { server, share }) if !server.is_empty() && !share.is_empty() => {
+            // Node's root stops after `\\?\UNC\`, so skip the server and share as well.
+            return to_posix(&format!("\\\\{}", rest), drives);
+        }
+        Some(Prefix::RootedWithoutDrive) => "".to_owned(),
+        Some(Prefix::DriveRelative(_))
+        | Some(Prefix::VerbatimUnc { .. })
+        | Some(Prefix::DeviceNamespace(_)) => return None,
+    };
+
+    // Normalize as an absolute path so `..` can't climb above the root.
+    let rest = posix::normalize(&format!("/{}", rest.replace(win32::sep, "/")));
+    if root.is_empty() {
+        // `\c` would come back as a drive.
+        Some(rest).filter(|rest| split_drive(rest, drives).is_none())
+    } else if rest == "/" {
+        Some(root)
+    } else {
+        Some(root + &rest)
+    }
+}
+
+/// The drive letter of a normalized absolute posix path, and the rest of the path.
+fn split_drive<'a>(path: &'a str, drives: DriveMapping) -> Option<(char, &'a str)> {
+    path.strip_prefix(drives.prefix().trim_end_matches('/'))
+        .and_then(|rest| rest.strip_prefix('/'))
+        .and_then(|rest| {
+            let (letter, rest) = rest.split_at(rest.find('/').unwrap_or(rest.len()));
+            match letter.as_bytes() {
+                [letter] if letter.is_ascii_alphabetic() => Some((*letter as char, rest)),
+                _ => None,
+            }
+        })
+}
+
+fn drive_root(letter: char, drives: DriveMapping) -> String {
+    format!("{}{}", drives.prefix(), letter.to_ascii_lowercase())
+}
+
+/// Converts a posix path to win32, the reverse of [`to_posix`].
+///
+/// Drive letters are recognized with `drives` and uppercased, and a leading `//server/share` becomes a UNC root.
+/// Any other absolute path is rooted without a drive. Returns `None` if a segment contains `\`, which win32 would
+/// read as a separator, or if a relative path would read as a drive-relative one.
+/// ```rust
+/// use nodejs_path::{to_win32, DriveMapping};
+///
+/// assert_eq!(to_win32("/c/Users/x", DriveMapping::Msys).unwrap(), "C:\\Users\\x");
+/// assert_eq!(to_win32("/mnt/c", DriveMapping::Wsl).unwrap(), "C:\\");
+/// assert_eq!(to_win32("//srv/share/a", DriveMapping::Msys).unwrap(), "\\\\srv\\share\\a");
+/// assert_eq!(to_win32("/usr/bin", DriveMapping::Wsl).unwrap(), "\\usr\\bin");
+/// assert_eq!(to_win32("a/../b/", DriveMapping::Msys).unwrap(), "b\\");
+/// ```
+pub fn to_win32(path: &str, drives: DriveMapping) -> Option<String> {
+    if path.contains(win32::sep) {
+        return None;
+    }
+
+    // `posix::normalize` collapses the leading `//` of a UNC root, so it is matched first.
+    let bytes = path.as_bytes();
+    if bytes.len() > 2 && bytes[0] == b'/' && bytes[1] == b'/' && bytes[2] != b'/' {
+        let mut parts = path[2..].splitn(3, '/');
+        if let (Some(server), Some(share)) = (parts.next(), parts.next()) {
+            if !share.is_empty() {
+                let rest = match parts.next() {
+                    Some(rest) => posix::normalize(&format!("/{}", rest)).replace('/', "\\"),
+                    None => "".to_owned(),
+                };
+                return Some(format!("\\\\{}\\{}{}", server, share, rest));
+            }
+        }
+    }
+
+    let normalized = posix::normalize(path);
+    if !posix::is_absolute(&normalized) {
+        // `c:x` is a file name on posix, but a drive-relative path on win32.
+        return Some(normalized.replace('/', "\\")).filter(|path| win32::prefix(path).is_none());
+    }
+
+    let drive = split_drive(&normalized, drives);
+    Some(match drive {
+        Some((letter, rest)) => format!(
+            "{}:\\{}",
+            letter.to_ascii_uppercase(),
+            rest.trim_start_matches('/').replace('/', "\\")
+        ),
+        None => normalized.replace('/', "\\"),
+    })
+}
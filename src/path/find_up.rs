@@ -0,0 +1,137 @@
+use super::{posix, win32};
+
+/// How [`find_up`] walks and what counts as a match.
+///
+/// By default it stops at the first match, walks posix paths up to the root, and checks the real filesystem.
+pub struct FindUpOptions<'a> {
+    all: bool,
+    stop_at: Option<String>,
+    win32: bool,
+    exists: Box<dyn Fn(&str) -> bool + 'a>,
+}
+
+impl<'a> FindUpOptions<'a> {
+    pub fn new() -> Self {
+        Self {
+            all: false,
+            stop_at: None,
+            win32: false,
+            exists: Box::new(|path| std::path::Path::new(path).exists()),
+        }
+    }
+
+    /// Collects every match instead of stopping at the first one.
+    pub fn all(mut self, all: bool) -> Self {
+        self.all = all;
+        self
+    }
+
+    /// The last directory to look in. Like the start directory, it is resolved against [`posix::cwd`] for posix
+    /// paths, and compared as written apart from trailing separators with [`FindUpOptions::win32`].
+    pub fn stop_at(mut self, dir: &str) -> Self {
+        self.stop_at = Some(dir.to_owned());
+        self
+    }
+
+    /// Walks with [`win32::dirname`] and joins with `\` instead of using posix paths. There is no win32 current
+    /// directory to resolve against, so the start directory should be absolute: a relative one is only walked up to
+    /// `.`.
+    pub fn win32(mut self, win32: bool) -> Self {
+        self.win32 = win32;
+        self
+    }
+
+    /// Replaces the filesystem check, for virtual trees or to only accept files.
+    pub fn exists(mut self, exists: impl Fn(&str) -> bool + 'a) -> Self {
+        self.exists = Box::new(exists);
+        self
+    }
+
+    fn trim_separators<'p>(&self, path: &'p str) -> &'p str {
+        if self.win32 {
+            path.trim_end_matches(['/', '\\'])
+        } else {
+            path.trim_end_matches('/')
+        }
+    }
+}
+
+impl Default for FindUpOptions<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Looks for any of `names` in the directory `start` and then in each of its ancestors, like the `find-up` package.
+///
+/// Within a directory, `names` are tried in order. The walk ends at the first match, unless [`FindUpOptions::all`]
+/// is set, and otherwise at the stop directory or the root. Matches are returned nearest first. A relative posix
+/// `start` is resolved against [`posix::cwd`] first, so the walk still reaches the root.
+/// ```rust
+/// use nodejs_path::{find_up, FindUpOptions};
+///
+/// let files = ["/repo/tsconfig.json", "/repo/.editorconfig", "/repo/packages/app/tsconfig.json"];
+/// let exists = |path: &str| files.contains(&path);
+///
+/// let nearest = find_up(
+///     "/repo/packages/app/src",
+///     &["tsconfig.json"],
+///     &FindUpOptions::new().exists(exists),
+/// );
+/// assert_eq!(nearest, ["/repo/packages/app/tsconfig.json"]);
+///
+/// let all = find_up(
+///     "/repo/packages/app/src",
+///     &["tsconfig.json", ".editorconfig"],
+///     &FindUpOptions::new().all(true).exists(exists),
+/// );
+/// assert_eq!(all, ["/repo/packages/app/tsconfig.json", "/repo/tsconfig.json", "/repo/.editorconfig"]);
+///
+/// let stopped = find_up(
+///     "/repo/packages/app/src",
+///     &[".editorconfig"],
+///     &FindUpOptions::new().stop_at("/repo/packages").exists(exists),
+/// );
+/// assert!(stopped.is_empty());
+/// ```
+pub fn find_up<S: AsRef<str>>(start: &str, names: &[S], options: &FindUpOptions) -> Vec<String> {
+    let (sep, dirname): (char, fn(&str) -> String) = if options.win32 {
+        ('\\', win32::dirname)
+    } else {
+        ('/', posix::dirname)
+    };
+    let absolute = |path: &str| {
+        if options.win32 {
+            path.to_owned()
+        } else {
+            posix::resolve_impl(&[path])
+        }
+    };
+    let stop_at = options.stop_at.as_deref().map(absolute);
+    let stop_at = stop_at
+        .as_deref()
+        .map(|stop_at| options.trim_separators(stop_at));
+
+    let mut found = Vec::new();
+    let mut dir = absolute(start);
+    loop {
+        let trimmed = options.trim_separators(&dir);
+        for name in names {
+            let candidate = format!("{}{}{}", trimmed, sep, name.as_ref());
+            if (options.exists)(&candidate) {
+                found.push(candidate);
+                if !options.all {
+                    return found;
+                }
+            }
+        }
+        if stop_at == Some(trimmed) {
+            return found;
+        }
+        let parent = dirname(&dir);
+        if parent == dir {
+            return found;
+        }
+        dir = parent;
+    }
+}
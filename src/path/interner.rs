@@ -0,0 +1,205 @@
+use std::{
+    collections::HashMap,
+    sync::{Arc, RwLock},
+};
+
+use super::posix;
+
+/// A handle to a path interned in a [`PathInterner`]. Only meaningful for the interner that returned it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct PathId(u32);
+
+#[derive(Debug)]
+struct Entry {
+    path: Arc<str>,
+    parent: Option<PathId>,
+    // Number of segments below the root, or below `.` for relative paths.
+    depth: u32,
+}
+
+#[derive(Debug, Default)]
+struct Table {
+    ids: HashMap<Arc<str>, PathId>,
+    entries: Vec<Entry>,
+}
+
+/// Deduplicates [`posix::normalize`]d paths and hands out [`PathId`]s for them.
+///
+/// Every interned path also interns its parent, so [`PathInterner::parent`] and [`PathInterner::relative`] walk
+/// cached links instead of scanning strings. The interner can be shared between threads.
+///
+/// Paths are keyed by their normalized form without the trailing separator, so `/a/b/`, `/a//b` and `/a/c/../b`
+/// all get the same id.
+/// ```rust
+/// use nodejs_path::PathInterner;
+///
+/// let paths = PathInterner::new();
+/// let file = paths.intern("/project/src/../lib/index.js");
+/// assert_eq!(&*paths.path(file), "/project/lib/index.js");
+///
+/// let lib = paths.parent(file).unwrap();
+/// assert_eq!(lib, paths.intern("/project/lib/"));
+/// assert_eq!(paths.join(lib, "index.js"), file);
+///
+/// let test = paths.intern("/project/test");
+/// assert_eq!(paths.relative(test, file), "../lib/index.js");
+/// ```
+#[derive(Debug, Default)]
+pub struct PathInterner {
+    table: RwLock<Table>,
+}
+
+impl PathInterner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Normalizes `path` and returns its id, interning it and its ancestors if needed.
+    pub fn intern(&self, path: &str) -> PathId {
+        let normalized = posix::normalize(path);
+        self.intern_normalized(trim_trailing_separator(&normalized))
+    }
+
+    /// The id of `path` if it has been interned already.
+    pub fn get(&self, path: &str) -> Option<PathId> {
+        let normalized = posix::normalize(path);
+        self.lookup(trim_trailing_separator(&normalized))
+    }
+
+    /// The normalized path behind `id`.
+    pub fn path(&self, id: PathId) -> Arc<str> {
+        self.read(|table| table.entries[id.0 as usize].path.clone())
+    }
+
+    /// Same as `dirname`, or `None` for `/` and `.`.
+    pub fn parent(&self, id: PathId) -> Option<PathId> {
+        self.read(|table| table.entries[id.0 as usize].parent)
+    }
+
+    /// Same as `join(path, segment)`.
+    pub fn join(&self, id: PathId, segment: &str) -> PathId {
+        let path = self.path(id);
+        let is_plain = !segment.is_empty()
+            && segment != "."
+            && segment != ".."
+            && !segment.contains(posix::sep);
+        if is_plain && &*path != "." {
+            // Already normalized, so skip `normalize` and its allocations when the child is known.
+            let joined = if &*path == "/" {
+                format!("/{}", segment)
+            } else {
+                format!("{}/{}", path, segment)
+            };
+            self.lookup(&joined)
+                .unwrap_or_else(|| self.insert(&joined, Some(id)))
+        } else {
+            self.intern(&posix::join_impl(&[&*path, segment]))
+        }
+    }
+
+    /// Same as `relative(from, to)`. Absolute paths are compared by walking parent links. As soon as one path is
+    /// relative, both are resolved against [`posix::cwd`] like `relative` does.
+    pub fn relative(&self, from: PathId, to: PathId) -> String {
+        if from == to {
+            return String::new();
+        }
+        let (from_path, to_path) = (self.path(from), self.path(to));
+        if !posix::is_absolute(&from_path) || !posix::is_absolute(&to_path) {
+            return posix::relative(&from_path, &to_path);
+        }
+
+        self.read(|table| {
+            let entry = |id: PathId| &table.entries[id.0 as usize];
+            let (mut a, mut b) = (from, to);
+            let mut ups = 0;
+            while entry(a).depth > entry(b).depth {
+                a = entry(a).parent.unwrap();
+                ups += 1;
+            }
+            while entry(b).depth > entry(a).depth {
+                b = entry(b).parent.unwrap();
+            }
+            while a != b {
+                a = entry(a).parent.unwrap();
+                b = entry(b).parent.unwrap();
+                ups += 1;
+            }
+
+            let mut out = vec![".."; ups].join("/");
+            let rest = to_path[entry(a).path.len()..].trim_start_matches('/');
+            if !out.is_empty() && !rest.is_empty() {
+                out.push('/');
+            }
+            out.push_str(rest);
+            out
+        })
+    }
+
+    /// Number of interned paths.
+    pub fn len(&self) -> usize {
+        self.read(|table| table.entries.len())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn read<T>(&self, f: impl FnOnce(&Table) -> T) -> T {
+        f(&self.table.read().unwrap())
+    }
+
+    fn lookup(&self, key: &str) -> Option<PathId> {
+        self.read(|table| table.ids.get(key).copied())
+    }
+
+    fn intern_normalized(&self, key: &str) -> PathId {
+        // Walks up to the nearest interned ancestor in a loop rather than recursing, so deep paths can't overflow
+        // the stack, then inserts the missing ones from the top down.
+        let mut missing = Vec::new();
+        let mut dir = key.to_owned();
+        let mut parent = loop {
+            if let Some(id) = self.lookup(&dir) {
+                break Some(id);
+            }
+            let up = match dir.as_str() {
+                "/" | "." => None,
+                _ => Some(posix::dirname(&dir)),
+            };
+            missing.push(dir);
+            match up {
+                Some(up) => dir = up,
+                None => break None,
+            }
+        };
+        while let Some(dir) = missing.pop() {
+            parent = Some(self.insert(&dir, parent));
+        }
+        parent.expect("a path is interned or has ancestors to insert")
+    }
+
+    fn insert(&self, key: &str, parent: Option<PathId>) -> PathId {
+        let mut table = self.table.write().unwrap();
+        // Another thread may have won the race since `lookup`.
+        if let Some(id) = table.ids.get(key) {
+            return *id;
+        }
+        let id =
+            PathId(u32::try_from(table.entries.len()).expect("more than u32::MAX interned paths"));
+        let depth = parent.map_or(0, |parent| table.entries[parent.0 as usize].depth + 1);
+        let path: Arc<str> = Arc::from(key);
+        table.entries.push(Entry {
+            path: path.clone(),
+            parent,
+            depth,
+        });
+        table.ids.insert(path, id);
+        id
+    }
+}
+
+fn trim_trailing_separator(path: &str) -> &str {
+    match path.trim_end_matches(posix::sep) {
+        "" if !path.is_empty() => "/",
+        trimmed => trimmed,
+    }
+}
@@ -5,4 +5,10 @@ pub mod posix;
 /// `nodejs_path::win32` provides access to  Windows-specific implementations of the path methods.
 pub mod win32;
 
+pub(crate) mod components;
+pub(crate) mod convert;
+#[cfg(feature = "std")]
+pub(crate) mod find_up;
+#[cfg(feature = "std")]
+pub(crate) mod interner;
 pub(crate) mod shared;
@@ -1,7 +1,16 @@
-use std::{borrow::Cow, ops::Add, path::PathBuf};
+use alloc::{
+    borrow::{Cow, ToOwned},
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::ops::Add;
 
 use crate::Parsed;
 
+use super::components::{Component, Components, Flavor};
+
 use super::shared::{
     format_inner, is_posix_path_separator, normalize_string, CHAR_DOT, CHAR_FORWARD_SLASH,
 };
@@ -41,7 +50,8 @@ pub fn basename_impl_without_ext(path: &str, ext: &str) -> String {
     let path = path.chars().collect::<Vec<char>>();
     let ext = ext.chars().collect::<Vec<char>>();
 
-    if ext.len() > 0 && ext.len() <= path.len() {
+    // Node compares lengths in UTF-16 code units. Matching from the end gives the same result by chars.
+    if !ext.is_empty() && utf16_len(&ext) <= utf16_len(&path) {
         if ext == path {
             return "".to_owned();
         }
@@ -118,7 +128,7 @@ pub fn basename_impl_without_ext(path: &str, ext: &str) -> String {
         return "".to_owned();
     }
 
-    return path[start as usize..end as usize].iter().collect();
+    path[start as usize..end as usize].iter().collect()
 }
 
 /// Returns the last portion of a path, similar to the Unix basename command. Trailing directory separators are ignored.
@@ -129,7 +139,6 @@ pub fn basename_impl_without_ext(path: &str, ext: &str) -> String {
 ///
 /// assert_eq!(&nodejs_path::basename!("/foo/bar/baz/asdf/quux.HTML", ".html"), "quux.HTML");
 /// ```
-
 #[macro_export]
 macro_rules! basename {
     (  $x:expr  ) => {{
@@ -146,13 +155,12 @@ pub use basename;
 /// assert_eq!(&nodejs_path::dirname("/foo/bar/baz/asdf/quux"), "/foo/bar/baz/asdf");
 /// ```
 pub fn dirname(path: &str) -> String {
-    if path.len() == 0 {
+    if path.is_empty() {
         ".".to_owned()
     } else {
         let path = path.chars().collect::<Vec<char>>();
         let has_root = path
-            .iter()
-            .next()
+            .first()
             .map(|c| c == &CHAR_FORWARD_SLASH)
             .unwrap_or(false);
         let mut end = -1;
@@ -190,6 +198,11 @@ pub fn dirname(path: &str) -> String {
         }
     }
 }
+
+fn utf16_len(chars: &[char]) -> usize {
+    chars.iter().map(|c| c.len_utf16()).sum()
+}
+
 /// Returns the extension of the path, from the last occurrence of the . (period) character to end of string in the last portion of the path. If there is no . in the last portion of the path, or if there are no . characters other than the first character of the basename of path, an empty string is returned.
 /// ```rust
 /// assert_eq!(&nodejs_path::extname("index.html"), ".html");
@@ -203,15 +216,79 @@ pub fn dirname(path: &str) -> String {
 /// assert_eq!(&nodejs_path::extname(".index.md"), ".md");
 /// ```
 pub fn extname(path: &str) -> String {
-    parse(path).ext
+    // `parse` only looks after the root, so it has its own take on paths like `/..`.
+    let path = path.as_bytes();
+    let mut start_dot = -1;
+    let mut start_part = 0;
+    let mut end = -1;
+    let mut matched_slash = true;
+    // Track the state of characters (if any) we see before our first dot and
+    // after any path separator we find
+    let mut pre_dot_state = 0;
+
+    for i in (0..path.len() as i32).rev() {
+        let code = path[i as usize] as char;
+        if code == CHAR_FORWARD_SLASH {
+            // If we reached a path separator that was not part of a set of path
+            // separators at the end of the string, stop now
+            if !matched_slash {
+                start_part = i + 1;
+                break;
+            }
+            continue;
+        }
+        if end == -1 {
+            // We saw the first non-path separator, mark this as the end of our
+            // extension
+            matched_slash = false;
+            end = i + 1;
+        }
+        if code == CHAR_DOT {
+            // If this is our first dot, mark it as the start of our extension
+            if start_dot == -1 {
+                start_dot = i;
+            } else if pre_dot_state != 1 {
+                pre_dot_state = 1;
+            }
+        } else if start_dot != -1 {
+            // We saw a non-dot and non-path separator before our dot, so we should
+            // have a good chance at having a non-empty extension
+            pre_dot_state = -1;
+        }
+    }
+
+    if start_dot == -1
+        || end == -1
+        // We saw a non-dot character immediately before the dot
+        || pre_dot_state == 0
+        // The (right-most) trimmed path component is exactly '..'
+        || (pre_dot_state == 1 && start_dot == end - 1 && start_dot == start_part + 1)
+    {
+        return "".to_owned();
+    }
+    String::from_utf8_lossy(&path[start_dot as usize..end as usize]).into_owned()
 }
 
 /// Returns a path string from an object. This is the opposite of nodejs_path::parse().
-
 pub fn format(path_object: Parsed) -> String {
     format_inner("/", path_object)
 }
 
+/// Iterates over the components of `path` without allocating. See [`Components`].
+/// ```rust
+/// use nodejs_path::Component;
+///
+/// let components = nodejs_path::posix::components("/foo/./bar/").collect::<Vec<_>>();
+/// assert_eq!(components, [Component::Root("/"), Component::Normal("foo"), Component::CurDir, Component::Normal("bar")]);
+///
+/// let components = nodejs_path::posix::components("foo/../bar").lexical().collect::<Vec<_>>();
+/// assert_eq!(components, [Component::Normal("bar")]);
+/// ```
+pub fn components(path: &str) -> Components<'_> {
+    let root_len = if is_absolute(path) { 1 } else { 0 };
+    Components::new(path, Flavor::Posix, root_len, root_len > 0)
+}
+
 /// The method determines if path is an absolute path. If the given path is a zero-length string, false will be returned.
 /// #Example
 /// ```rust
@@ -252,7 +329,7 @@ macro_rules! join {
 pub use join;
 
 pub fn join_impl<T: AsRef<str>>(args: &[T]) -> String {
-    if args.len() == 0 {
+    if args.is_empty() {
         ".".to_owned()
     } else {
         // let length =
@@ -288,8 +365,8 @@ pub fn join_impl<T: AsRef<str>>(args: &[T]) -> String {
 /// assert_eq!(nodejs_path::posix::normalize("/foo/bar//baz/asdf/quux/.."), "/foo/bar/baz/asdf");
 /// ```
 pub fn normalize(path: &str) -> String {
-    if path.len() == 0 {
-        return ".".to_owned();
+    if path.is_empty() {
+        ".".to_owned()
     } else {
         let is_absolute = is_absolute(path);
         let trailing_separator = path
@@ -368,10 +445,10 @@ pub fn normalize(path: &str) -> String {
 pub fn parse(path: &str) -> Parsed {
     let path = path.chars().collect::<Vec<char>>();
     let mut ret = Parsed::default();
-    if path.len() == 0 {
+    if path.is_empty() {
         ret
     } else {
-        let is_absolute = path.get(0).map(|c| c == &CHAR_FORWARD_SLASH).unwrap();
+        let is_absolute = path.first().map(|c| c == &CHAR_FORWARD_SLASH).unwrap();
 
         let start;
         if is_absolute {
@@ -464,95 +541,170 @@ pub fn parse(path: &str) -> Parsed {
 /// ```rust
 /// assert_eq!(nodejs_path::posix::relative("/data/orandea/test/aaa", "/data/orandea/impl/bbb"), "../../impl/bbb");
 /// ```
+#[cfg(feature = "std")]
+#[inline]
 pub fn relative(from: &str, to: &str) -> String {
-    if from == to {
-        "".to_owned()
-    } else {
-        let from = resolve!(&from).chars().collect::<Vec<char>>();
-        let to = resolve!(&to).chars().collect::<Vec<char>>();
+    relative_with_cwd(from, to, cwd())
+}
 
-        if from == to {
-            "".to_owned()
-        } else {
-            let from_start = 1;
-            let from_end = from.len() as i32;
-            let from_len = from_end - from_start;
-            let to_start = 1;
-            let to_len = to.len() as i32 - to_start;
+/// Same as [`relative`], but relative `from` and `to` are resolved against the given `cwd` instead of [`cwd()`].
+/// ```rust
+/// assert_eq!(nodejs_path::posix::relative_with_cwd("a/b", "a/c", "/tmp"), "../c");
+/// assert_eq!(nodejs_path::posix::relative_with_cwd("/tmp/a", "b", "/tmp"), "../b");
+/// ```
+pub fn relative_with_cwd(from: &str, to: &str, cwd: &str) -> String {
+    let mut out = "".to_owned();
+    if from != to {
+        let from = resolve_impl_with_cwd(&[from], cwd);
+        let to = resolve_impl_with_cwd(&[to], cwd);
+        relative_resolved(&from, &to, &mut out);
+    }
+    out
+}
 
-            // Compare paths to find the longest common path from root
-            let length = if from_len < to_len { from_len } else { to_len };
+/// The result of [`relative_physical`].
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PhysicalRelative {
+    /// A relative path from `from` to `to` that is correct on the filesystem.
+    pub path: String,
+    /// Whether `resolve!(from, path)` also leads to `to`, so string-based tools agree with the filesystem. This is
+    /// `false` when `from` is reached through a symlink and neither the lexical nor the physical path works both ways.
+    pub lexically_equivalent: bool,
+}
 
-            let mut last_common_sep = -1;
-            let mut i = 0;
+/// Same as [`relative`], but follows symlinks. Both ends must exist, since they are canonicalized through the
+/// filesystem. The lexical [`relative`] path is returned if it is also correct on the filesystem, otherwise the
+/// path between the canonical ends, which is what Node uses when resolving imports.
+/// ```rust
+/// let dir = std::env::temp_dir();
+/// let relative = nodejs_path::posix::relative_physical(dir.to_str().unwrap(), "/").unwrap();
+/// assert!(relative.path.starts_with(".."));
+/// ```
+#[cfg(feature = "std")]
+pub fn relative_physical(from: &str, to: &str) -> std::io::Result<PhysicalRelative> {
+    let (from, to) = (resolve_impl(&[from]), resolve_impl(&[to]));
+    let (physical_from, physical_to) = (canonicalize(&from)?, canonicalize(&to)?);
+    let leads_to_physical_to = |base: &str, path: &str| {
+        canonicalize(&resolve_impl(&[base, path])).ok().as_ref() == Some(&physical_to)
+    };
 
-            while i < length {
-                let from_code = from.get((from_start + i) as usize).unwrap();
-                if from_code != to.get((to_start + i) as usize).unwrap() {
-                    break;
-                } else if from_code == &CHAR_FORWARD_SLASH {
-                    last_common_sep = i;
-                }
-                i += 1;
-            }
+    let lexical = relative(&from, &to);
+    if leads_to_physical_to(&physical_from, &lexical) {
+        return Ok(PhysicalRelative {
+            path: lexical,
+            lexically_equivalent: true,
+        });
+    }
 
-            if i == length {
-                if to_len > length {
-                    if to.get((to_start + i) as usize).unwrap() == &CHAR_FORWARD_SLASH {
-                        // We get here if `from` is the exact base path for `to`.
-                        // For example: from='/foo/bar'; to='/foo/bar/baz'
-                        return to[(to_start + i + 1) as usize..to.len()].iter().collect();
-                        // return StringPrototypeSlice(to, toStart + i + 1);
-                    }
-                    if i == 0 {
-                        // We get here if `from` is the root
-                        // For example: from='/'; to='/foo'
-                        return to[(to_start + i) as usize..to.len()].iter().collect();
-                    }
-                } else if from_len > length {
-                    if from.get((from_start + i) as usize).unwrap() == &CHAR_FORWARD_SLASH {
-                        // We get here if `to` is the exact base path for `from`.
-                        // For example: from='/foo/bar/baz'; to='/foo/bar'
-                        last_common_sep = i;
-                    } else if i == 0 {
-                        // We get here if `to` is the root.
-                        // For example: from='/foo/bar'; to='/'
-                        last_common_sep = 0;
-                    }
-                }
-            }
+    let physical = relative(&physical_from, &physical_to);
+    Ok(PhysicalRelative {
+        lexically_equivalent: leads_to_physical_to(&from, &physical),
+        path: physical,
+    })
+}
 
-            let mut out = "".to_owned();
-            // Generate the relative path based on the path difference between `to`
-            // and `from`.
-            let mut i = from_start + last_common_sep + 1;
-            while i <= from_end {
-                if i == from_end || from.get(i as usize).unwrap() == &CHAR_FORWARD_SLASH {
-                    if out.len() == 0 {
-                        out.push_str("..")
-                    } else {
-                        out.push_str("/..")
-                    }
-                    // out += out.length === 0 ? '..' : '/..';
-                }
-                i += 1;
+#[cfg(feature = "std")]
+pub(crate) fn canonicalize(path: &str) -> std::io::Result<String> {
+    std::fs::canonicalize(path)?
+        .into_os_string()
+        .into_string()
+        .map_err(|path| {
+            std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                format!("{:?} is not valid UTF-8", path),
+            )
+        })
+}
+
+/// Pushes the relative path from `from` to `to` onto `out`. Both must already be resolved, absolute paths.
+fn relative_resolved(from: &str, to: &str, out: &mut String) {
+    if from == to {
+        return;
+    }
+    // Separators are ASCII, so comparing bytes only ever stops on a char boundary where it matters.
+    let (from, to) = (from.as_bytes(), to.as_bytes());
+    let from_start = 1;
+    let from_end = from.len() as i32;
+    let from_len = from_end - from_start;
+    let to_start = 1;
+    let to_len = to.len() as i32 - to_start;
+
+    // Compare paths to find the longest common path from root
+    let length = if from_len < to_len { from_len } else { to_len };
+
+    let mut last_common_sep = -1;
+    let mut i = 0;
+
+    while i < length {
+        let from_code = from[(from_start + i) as usize];
+        if from_code != to[(to_start + i) as usize] {
+            break;
+        } else if from_code == CHAR_FORWARD_SLASH as u8 {
+            last_common_sep = i;
+        }
+        i += 1;
+    }
+
+    let slice = |start: i32| core::str::from_utf8(&to[start as usize..]).unwrap();
+
+    if i == length {
+        if to_len > length {
+            if to[(to_start + i) as usize] == CHAR_FORWARD_SLASH as u8 {
+                // We get here if `from` is the exact base path for `to`.
+                // For example: from='/foo/bar'; to='/foo/bar/baz'
+                out.push_str(slice(to_start + i + 1));
+                return;
+            }
+            if i == 0 {
+                // We get here if `from` is the root
+                // For example: from='/'; to='/foo'
+                out.push_str(slice(to_start + i));
+                return;
             }
+        } else if from_len > length {
+            if from[(from_start + i) as usize] == CHAR_FORWARD_SLASH as u8 {
+                // We get here if `to` is the exact base path for `from`.
+                // For example: from='/foo/bar/baz'; to='/foo/bar'
+                last_common_sep = i;
+            } else if i == 0 {
+                // We get here if `to` is the root.
+                // For example: from='/foo/bar'; to='/'
+                last_common_sep = 0;
+            }
+        }
+    }
 
-            // Lastly, append the rest of the destination (`to`) path that comes after
-            // the common path parts.
-            format!(
-                "{}{}",
-                &out,
-                &to[(to_start + last_common_sep) as usize..to.len()]
-                    .iter()
-                    .collect::<String>()
-            )
-            // return `${out}${StringPrototypeSlice(to, toStart + lastCommonSep)}`;
+    // Generate the relative path based on the path difference between `to`
+    // and `from`.
+    let mut first = true;
+    let mut i = from_start + last_common_sep + 1;
+    while i <= from_end {
+        if i == from_end || from[i as usize] == CHAR_FORWARD_SLASH as u8 {
+            out.push_str(if first { ".." } else { "/.." });
+            first = false;
         }
+        i += 1;
     }
+
+    // Lastly, append the rest of the destination (`to`) path that comes after
+    // the common path parts.
+    out.push_str(slice(to_start + last_common_sep));
 }
 
+#[cfg(feature = "std")]
+#[inline]
 pub fn resolve_impl<T: AsRef<str>>(args: &[T]) -> String {
+    resolve_impl_with_cwd(args, cwd())
+}
+
+/// Same as [`resolve_impl`], but falls back to the given `cwd` instead of [`cwd()`] when `args` don't form an
+/// absolute path.
+/// ```rust
+/// assert_eq!(nodejs_path::posix::resolve_impl_with_cwd(&["a/b", "../c"], "/tmp"), "/tmp/a/c");
+/// assert_eq!(nodejs_path::posix::resolve_impl_with_cwd(&["/var/lib", "../", "file/"], "/tmp"), "/var/file");
+/// ```
+pub fn resolve_impl_with_cwd<T: AsRef<str>>(args: &[T], cwd: &str) -> String {
     let mut resolved_path = "".to_owned();
     let mut resolved_absolute = false;
 
@@ -560,13 +712,13 @@ pub fn resolve_impl<T: AsRef<str>>(args: &[T]) -> String {
 
     while i >= -1 && !resolved_absolute {
         let path = if i >= 0 {
-            args.get(i.clone() as usize).unwrap().as_ref().to_string()
+            args.get(i as usize).unwrap().as_ref().to_string()
         } else {
-            cwd().to_owned()
+            cwd.to_owned()
         };
 
         // Skip empty entries
-        if path.len() == 0 {
+        if path.is_empty() {
             i -= 1;
             continue;
         }
@@ -616,6 +768,7 @@ pub fn resolve_impl<T: AsRef<str>>(args: &[T]) -> String {
 ///
 /// assert_eq!(nodejs_path::resolve!(), std::env::current_dir().unwrap().to_str().unwrap().to_owned());
 /// ```
+#[cfg(feature = "std")]
 #[macro_export]
 macro_rules! resolve {
     (  ) => {
@@ -633,15 +786,158 @@ macro_rules! resolve {
       }
     };
   }
+#[cfg(feature = "std")]
 pub use resolve;
 
+/// A base directory that is resolved and normalized once, to resolve many paths against it.
+///
+/// [`resolve!`] re-normalizes every argument and reads [`cwd()`] on each call. `BaseDir` only walks the new path,
+/// and [`BaseDir::resolve_many`] and [`BaseDir::relative_many`] reuse the same buffers for every path.
+/// ```rust
+/// use nodejs_path::posix::BaseDir;
+///
+/// let mut base = BaseDir::with_cwd("src/../lib", "/project");
+/// assert_eq!(base.as_str(), "/project/lib");
+/// assert_eq!(base.resolve("./a/../b.js"), "/project/lib/b.js");
+/// assert_eq!(base.relative("/project/test"), "../test");
+///
+/// let mut resolved = vec![];
+/// base.resolve_many(["x.js", "../y.js", "/z.js"], |path| resolved.push(path.to_owned()));
+/// assert_eq!(resolved, ["/project/lib/x.js", "/project/y.js", "/z.js"]);
+/// ```
+#[derive(Debug, Clone)]
+pub struct BaseDir {
+    base: String,
+    cwd: String,
+    resolved: String,
+    out: String,
+}
+
+impl BaseDir {
+    /// Resolves `base` against [`cwd()`].
+    #[cfg(feature = "std")]
+    pub fn new(base: &str) -> Self {
+        Self::with_cwd(base, cwd())
+    }
+
+    /// Resolves `base` against the given `cwd`. `cwd` is also what [`BaseDir::relative`] resolves its argument
+    /// against, like [`relative_with_cwd`].
+    pub fn with_cwd(base: &str, cwd: &str) -> Self {
+        Self {
+            base: resolve_impl_with_cwd(&[base], cwd),
+            cwd: resolve_impl_with_cwd(&[cwd], ""),
+            resolved: String::new(),
+            out: String::new(),
+        }
+    }
+
+    /// The resolved base directory.
+    pub fn as_str(&self) -> &str {
+        &self.base
+    }
+
+    /// Same as `resolve!(base, path)`.
+    pub fn resolve(&self, path: &str) -> String {
+        let mut out = String::new();
+        self.resolve_into(path, &mut out);
+        out
+    }
+
+    /// Same as [`BaseDir::resolve`], but writes into `out` instead of allocating.
+    pub fn resolve_into(&self, path: &str, out: &mut String) {
+        out.clear();
+        // Only happens if the base could not be made absolute, e.g. when `cwd()` failed.
+        if !is_absolute(&self.base) {
+            out.push_str(&resolve_impl_with_cwd(&[self.base.as_str(), path], ""));
+            return;
+        }
+        resolve_onto(&self.base, path, out);
+    }
+
+    /// Same as `relative(base, to)`.
+    pub fn relative(&self, to: &str) -> String {
+        let (mut resolved, mut out) = (String::new(), String::new());
+        self.relative_into(to, &mut resolved, &mut out);
+        out
+    }
+
+    fn relative_into(&self, to: &str, resolved: &mut String, out: &mut String) {
+        out.clear();
+        if !is_absolute(&self.base) || !is_absolute(&self.cwd) {
+            out.push_str(&relative_with_cwd(&self.base, to, &self.cwd));
+            return;
+        }
+        resolved.clear();
+        resolve_onto(&self.cwd, to, resolved);
+        relative_resolved(&self.base, resolved, out);
+    }
+
+    /// Resolves every path against the base and passes the result to `f`. The `&str` is only valid during the call.
+    pub fn resolve_many<I, F>(&mut self, paths: I, mut f: F)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+        F: FnMut(&str),
+    {
+        let mut out = core::mem::take(&mut self.out);
+        for path in paths {
+            self.resolve_into(path.as_ref(), &mut out);
+            f(&out);
+        }
+        self.out = out;
+    }
+
+    /// Computes the relative path from the base to every path and passes it to `f`. The `&str` is only valid during
+    /// the call.
+    pub fn relative_many<I, F>(&mut self, paths: I, mut f: F)
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+        F: FnMut(&str),
+    {
+        let mut resolved = core::mem::take(&mut self.resolved);
+        let mut out = core::mem::take(&mut self.out);
+        for path in paths {
+            self.relative_into(path.as_ref(), &mut resolved, &mut out);
+            f(&out);
+        }
+        self.resolved = resolved;
+        self.out = out;
+    }
+}
+
+/// Pushes `path` resolved against `base` onto the empty `out`. `base` must already be resolved and absolute.
+fn resolve_onto(base: &str, path: &str, out: &mut String) {
+    if !is_absolute(path) {
+        out.push_str(base);
+    }
+    for component in components(path) {
+        match component {
+            Component::Root(_) => out.push('/'),
+            Component::CurDir => {}
+            Component::ParentDir => match out.rfind('/') {
+                Some(0) | None => out.truncate(1),
+                Some(i) => out.truncate(i),
+            },
+            Component::Normal(segment) => {
+                if !out.ends_with('/') {
+                    out.push('/');
+                }
+                out.push_str(segment);
+            }
+        }
+    }
+}
+
 pub fn to_namespaced_path() {}
 
+#[cfg(feature = "std")]
 use once_cell::sync::Lazy;
 
+#[cfg(feature = "std")]
 pub(crate) static POSIX_CWD: Lazy<String> = Lazy::new(|| {
     let mut cwd = std::env::current_dir()
-        .unwrap_or(PathBuf::from(""))
+        .unwrap_or(std::path::PathBuf::from(""))
         .to_string_lossy()
         .to_string();
     if cfg!(target_os = "windows") {
@@ -665,6 +961,7 @@ pub(crate) static POSIX_CWD: Lazy<String> = Lazy::new(|| {
 });
 
 /// Get current working directory. Just like `process.cwd()`
+#[cfg(feature = "std")]
 #[inline]
 pub fn cwd() -> &'static str {
     &POSIX_CWD
@@ -1,3 +1,5 @@
+use alloc::{borrow::ToOwned, format, string::String, vec, vec::Vec};
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Parsed {
     pub dir: String,
@@ -51,7 +53,7 @@ pub(crate) fn normalize_string(
             } else if dots == 2 {
                 if res.len() < 2
                     || last_segment_length != 2
-                    || res.get(res.len() - 1).unwrap() != &CHAR_DOT
+                    || res.last().unwrap() != &CHAR_DOT
                     || res.get(res.len() - 2).unwrap() != &CHAR_DOT
                 {
                     if res.len() > 2 {
@@ -71,7 +73,7 @@ pub(crate) fn normalize_string(
 
                         i += 1;
                         continue;
-                    } else if res.len() != 0 {
+                    } else if !res.is_empty() {
                         res = vec![];
                         last_segment_length = 0;
                         last_slash = i as i32;
@@ -82,7 +84,7 @@ pub(crate) fn normalize_string(
                     }
                 }
                 if allow_above_root {
-                    if res.len() > 0 {
+                    if !res.is_empty() {
                         res.push(*separator);
                     }
                     res.push('.');
@@ -90,10 +92,10 @@ pub(crate) fn normalize_string(
                     last_segment_length = 2;
                 }
             } else {
-                if res.len() > 0 {
+                if !res.is_empty() {
                     res.push(*separator)
                 }
-                path[(last_slash + 1) as usize..i as usize]
+                path[(last_slash + 1) as usize..i]
                     .iter()
                     .for_each(|c| res.push(*c));
                 last_segment_length = i as i32 - last_slash - 1;
@@ -112,7 +114,7 @@ pub(crate) fn normalize_string(
     res.into_iter().collect()
 }
 
-fn last_index_of(vec: &Vec<char>, tar: &char) -> Option<usize> {
+fn last_index_of(vec: &[char], tar: &char) -> Option<usize> {
     vec.iter()
         .enumerate()
         .rev()
@@ -124,6 +126,15 @@ pub(crate) fn is_empty(s: &str) -> bool {
     s.len() == 0
 }
 
+/// `ext` with a leading dot, which `format` adds since node 20.
+fn format_ext(ext: &str) -> String {
+    if ext.is_empty() || ext.starts_with('.') {
+        ext.to_owned()
+    } else {
+        format!(".{}", ext)
+    }
+}
+
 pub(crate) fn format_inner(sep: &str, path_object: Parsed) -> String {
     let root = path_object.root.clone();
     let dir = if !is_empty(&path_object.dir) {
@@ -134,7 +145,7 @@ pub(crate) fn format_inner(sep: &str, path_object: Parsed) -> String {
     let base = if !is_empty(&path_object.base) {
         path_object.base
     } else {
-        format!("{}{}", &path_object.name, &path_object.ext)
+        format!("{}{}", &path_object.name, format_ext(&path_object.ext))
     };
 
     if is_empty(&dir) {
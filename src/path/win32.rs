@@ -1,3 +1,6 @@
+use alloc::{borrow::ToOwned, string::String};
+
+use super::components::{Components, Flavor};
 use super::shared::{
     is_path_separator, CHAR_COLON, CHAR_LOWERCASE_A, CHAR_LOWERCASE_Z, CHAR_UPPERCASE_A,
     CHAR_UPPERCASE_Z,
@@ -15,7 +18,7 @@ pub const sep: char = '\\';
 pub const delimiter: char = ';';
 
 /// The method determines if path is an absolute path. If the given path is a zero-length string, false will be returned.
-/// 
+///
 /// #Example
 /// ```rust
 /// assert_eq!(nodejs_path::win32::is_absolute("//server"), true);
@@ -27,23 +30,226 @@ pub const delimiter: char = ';';
 /// assert_eq!(nodejs_path::win32::is_absolute("."), false);  
 /// ```
 pub fn is_absolute(path: &str) -> bool {
-    if path.is_empty() {
-        false
+    prefix(path).is_some_and(|prefix| prefix.is_absolute())
+}
+
+/// The kind of root a win32 path starts with, as returned by [`prefix`].
+///
+/// Node itself only distinguishes drives, UNC roots and single separators, so it treats the verbatim and device
+/// namespace forms as UNC roots whose server is `?` or `.`. For example, the root of `\\?\UNC\srv\share\x` is
+/// `\\?\UNC\` for `path.win32.parse()`. Separators may be either `\` or `/`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Prefix<'a> {
+    /// `C:\`
+    Drive(char),
+    /// `C:`, relative to the current directory of that drive.
+    DriveRelative(char),
+    /// `\\server\share`
+    Unc { server: &'a str, share: &'a str },
+    /// `\\?\C:\` or `\\?\GLOBALROOT\Device\...`, with the component following `\\?\`.
+    Verbatim(&'a str),
+    /// `\\?\UNC\server\share`. `server` and `share` are empty if they are missing.
+    VerbatimUnc { server: &'a str, share: &'a str },
+    /// `\\.\pipe\name` or `\\.\COM1`, with the component following `\\.\`.
+    DeviceNamespace(&'a str),
+    /// `\foo`, absolute on the current drive. Also covers `\\server` without a share.
+    RootedWithoutDrive,
+}
+
+impl<'a> Prefix<'a> {
+    /// Whether a path with this prefix is absolute, see [`is_absolute`].
+    pub fn is_absolute(&self) -> bool {
+        !matches!(self, Prefix::DriveRelative(_))
+    }
+}
+
+/// Classifies the root of `path`, or returns `None` for relative paths.
+/// ```rust
+/// use nodejs_path::win32::{prefix, Prefix};
+///
+/// assert_eq!(prefix("C:\\foo"), Some(Prefix::Drive('C')));
+/// assert_eq!(prefix("c:foo"), Some(Prefix::DriveRelative('c')));
+/// assert_eq!(prefix("\\\\srv\\share\\foo"), Some(Prefix::Unc { server: "srv", share: "share" }));
+/// assert_eq!(prefix("\\\\?\\C:\\foo"), Some(Prefix::Verbatim("C:")));
+/// assert_eq!(
+///     prefix("\\\\?\\UNC\\srv\\share"),
+///     Some(Prefix::VerbatimUnc { server: "srv", share: "share" })
+/// );
+/// assert_eq!(prefix("\\\\.\\pipe\\name"), Some(Prefix::DeviceNamespace("pipe")));
+/// assert_eq!(prefix("/foo"), Some(Prefix::RootedWithoutDrive));
+/// assert_eq!(prefix("foo\\bar"), None);
+/// ```
+pub fn prefix(path: &str) -> Option<Prefix<'_>> {
+    let bytes = path.as_bytes();
+    if bytes
+        .first()
+        .is_some_and(|b| is_path_separator(&(*b as char)))
+    {
+        return Some(match unc_root(path) {
+            Some(UncRoot {
+                server: "?",
+                share,
+                rest,
+            }) if share.eq_ignore_ascii_case("UNC") => {
+                let mut rest = rest
+                    .split(|c| is_path_separator(&c))
+                    .filter(|c| !c.is_empty());
+                let server = rest.next().unwrap_or("");
+                let share = rest.next().unwrap_or("");
+                Prefix::VerbatimUnc { server, share }
+            }
+            Some(UncRoot {
+                server: "?", share, ..
+            }) => Prefix::Verbatim(share),
+            Some(UncRoot {
+                server: ".", share, ..
+            }) => Prefix::DeviceNamespace(share),
+            Some(UncRoot { server, share, .. }) => Prefix::Unc { server, share },
+            None => Prefix::RootedWithoutDrive,
+        });
+    }
+    match drive_len(path) {
+        3 => Some(Prefix::Drive(bytes[0] as char)),
+        2 => Some(Prefix::DriveRelative(bytes[0] as char)),
+        _ => None,
+    }
+}
+
+/// A root of the form `\\server\share`, as Node finds it.
+struct UncRoot<'a> {
+    server: &'a str,
+    share: &'a str,
+    // Everything after the share and its separator.
+    rest: &'a str,
+}
+
+/// Node's UNC root matching: two separators, one or more non-separators, one or more separators and one or more
+/// non-separators.
+fn unc_root(path: &str) -> Option<UncRoot<'_>> {
+    let bytes = path.as_bytes();
+    let is_sep = |i: usize| {
+        bytes
+            .get(i)
+            .is_some_and(|b| is_path_separator(&(*b as char)))
+    };
+    let len = bytes.len();
+    if !(is_sep(0) && is_sep(1)) {
+        return None;
+    }
+
+    let server_start = 2;
+    let mut j = server_start;
+    while j < len && !is_sep(j) {
+        j += 1;
+    }
+    if j == len || j == server_start {
+        return None;
+    }
+    let server_end = j;
+    while j < len && is_sep(j) {
+        j += 1;
+    }
+    if j == len {
+        return None;
+    }
+    let share_start = j;
+    while j < len && !is_sep(j) {
+        j += 1;
+    }
+    Some(UncRoot {
+        server: &path[server_start..server_end],
+        share: &path[share_start..j],
+        rest: path.get(j + 1..).unwrap_or(""),
+    })
+}
+
+/// `3` for `C:\`, `2` for `C:`, `0` otherwise.
+fn drive_len(path: &str) -> usize {
+    let bytes = path.as_bytes();
+    if bytes.len() >= 2 && is_windows_device_root(bytes[0] as char) && bytes[1] == CHAR_COLON as u8
+    {
+        if bytes
+            .get(2)
+            .is_some_and(|b| is_path_separator(&(*b as char)))
+        {
+            3
+        } else {
+            2
+        }
     } else {
-        let path_len = path.len();
-        let mut path = path.chars();
-        let idx0 = path.next();
-        idx0.clone().map_or(false, |c| is_path_separator(&c))
-            || (path_len > 2 && idx0.map_or(false, |c| is_windows_device_root(c)) && {
-                let idx1 = path.next();
-                let idx2 = path.next();
-                idx1.map_or(false, |c| c == CHAR_COLON)
-                    && idx2.map_or(false, |c| is_path_separator(&c))
-            })
+        0
+    }
+}
+
+/// Returns the directory name of a path, like `path.win32.dirname()`. Both `\` and `/` are separators, and the
+/// root, a drive or a UNC root, is kept whole.
+/// ```rust
+/// assert_eq!(nodejs_path::win32::dirname("C:\\foo\\bar\\"), "C:\\foo");
+/// assert_eq!(nodejs_path::win32::dirname("C:\\foo"), "C:\\");
+/// assert_eq!(nodejs_path::win32::dirname("c:foo"), "c:");
+/// assert_eq!(nodejs_path::win32::dirname("\\\\server\\share\\file"), "\\\\server\\share\\");
+/// assert_eq!(nodejs_path::win32::dirname("foo/bar"), "foo");
+/// ```
+pub fn dirname(path: &str) -> String {
+    if path.is_empty() {
+        return ".".to_owned();
+    }
+    // Like Node, a path that is only a root, including a whole UNC root, is its own dirname.
+    let bytes = path.as_bytes();
+    let root_end = root_len(path);
+    let mut end = None;
+    let mut matched_slash = true;
+    for i in (root_end..bytes.len()).rev() {
+        if is_path_separator(&(bytes[i] as char)) {
+            if !matched_slash {
+                end = Some(i);
+                break;
+            }
+        } else {
+            matched_slash = false;
+        }
+    }
+    match end {
+        Some(end) => path[..end].to_owned(),
+        None if root_end > 0 => path[..root_end].to_owned(),
+        None => ".".to_owned(),
     }
 }
 
+/// Iterates over the components of `path` without allocating. The root is a drive, a UNC root or a single
+/// separator, and both `\` and `/` separate segments. See [`Components`].
+/// ```rust
+/// use nodejs_path::Component;
+///
+/// let components = nodejs_path::win32::components("C:\\foo/bar\\").collect::<Vec<_>>();
+/// assert_eq!(components, [Component::Root("C:\\"), Component::Normal("foo"), Component::Normal("bar")]);
+///
+/// let mut components = nodejs_path::win32::components("\\\\server\\share\\file.txt");
+/// assert_eq!(components.next(), Some(Component::Root("\\\\server\\share\\")));
+/// assert_eq!(components.next(), Some(Component::Normal("file.txt")));
+/// ```
+pub fn components(path: &str) -> Components<'_> {
+    let absolute = prefix(path).is_some_and(|prefix| prefix.is_absolute());
+    Components::new(path, Flavor::Win32, root_len(path), absolute)
+}
+
 fn is_windows_device_root(code: char) -> bool {
     (code >= CHAR_UPPERCASE_A && code <= CHAR_UPPERCASE_Z)
         || (code >= CHAR_LOWERCASE_A && code <= CHAR_LOWERCASE_Z)
 }
+
+/// Byte length of the root of `path`, as found by `path.win32.parse()`: a drive (`C:` or `C:\`), a UNC root
+/// (`\\server\share\`) or a single separator.
+pub(crate) fn root_len(path: &str) -> usize {
+    match prefix(path) {
+        None => 0,
+        Some(Prefix::Drive(_)) | Some(Prefix::DriveRelative(_)) => drive_len(path),
+        Some(Prefix::RootedWithoutDrive) => 1,
+        // Node doesn't look past the first two components, whatever the server is.
+        Some(_) => match unc_root(path) {
+            Some(UncRoot { rest: "", .. }) => path.len(),
+            Some(UncRoot { rest, .. }) => path.len() - rest.len(),
+            None => 1,
+        },
+    }
+}
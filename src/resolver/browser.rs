@@ -0,0 +1,112 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::posix;
+
+/// What the `browser` field replaces a file or package with.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BrowserTarget {
+    /// A file in the package, as an absolute normalized path.
+    Path(String),
+    /// Another package, as a bare specifier.
+    Module(String),
+    /// `false`: the import resolves to an empty module.
+    Empty,
+}
+
+/// The `browser` field of a `package.json`, following the
+/// [spec](https://github.com/defunctzombie/package-browser-field-spec) bundlers implement.
+///
+/// A string replaces `main`. An object maps files of the package and packages it imports to other files, packages
+/// or `false`. File keys and values are joined to the package directory, so `./lib/a.js`, `lib/a.js` and `lib/a`
+/// all name the same file.
+/// ```rust
+/// use nodejs_path::resolver::{BrowserField, BrowserTarget};
+///
+/// let browser = serde_json::json!({
+///     "./lib/node.js": "./lib/browser.js",
+///     "lib/server": false,
+///     "fs": false,
+///     "http": "stream-http",
+/// });
+/// let browser = BrowserField::new("/app/node_modules/pkg", &browser);
+/// assert_eq!(
+///     browser.map_path("/app/node_modules/pkg/lib/node.js"),
+///     Some(&BrowserTarget::Path("/app/node_modules/pkg/lib/browser.js".to_owned()))
+/// );
+/// assert_eq!(browser.map_path("/app/node_modules/pkg/lib/server.js"), Some(&BrowserTarget::Empty));
+/// assert_eq!(browser.map_module("fs"), Some(&BrowserTarget::Empty));
+/// assert_eq!(browser.map_module("http"), Some(&BrowserTarget::Module("stream-http".to_owned())));
+/// assert_eq!(browser.map_module("path"), None);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct BrowserField {
+    main: Option<String>,
+    paths: HashMap<String, BrowserTarget>,
+    modules: HashMap<String, BrowserTarget>,
+}
+
+impl BrowserField {
+    /// Reads the parsed `browser` value of the package in `package_dir`. Values of any other type, and entries that
+    /// are neither strings nor `false`, are ignored like bundlers do.
+    pub fn new(package_dir: &str, browser: &Value) -> Self {
+        let mut field = Self::default();
+        match browser {
+            Value::String(main) => field.main = Some(posix::join_impl(&[package_dir, main])),
+            Value::Object(map) => {
+                for (key, value) in map {
+                    let target = match value {
+                        Value::Bool(false) => BrowserTarget::Empty,
+                        Value::String(value) if is_path(value) => {
+                            BrowserTarget::Path(posix::join_impl(&[package_dir, value]))
+                        }
+                        Value::String(value) => BrowserTarget::Module(value.clone()),
+                        _ => continue,
+                    };
+                    // A bare key like `lib/a` may name a file of the package as well as a package, so it is kept
+                    // as both.
+                    if !is_path(key) {
+                        field.modules.insert(key.clone(), target.clone());
+                    }
+                    field
+                        .paths
+                        .insert(posix::join_impl(&[package_dir, key]), target);
+                }
+            }
+            _ => {}
+        }
+        field
+    }
+
+    /// The entry point replacing `main`, when the field is a string.
+    pub fn main(&self) -> Option<&str> {
+        self.main.as_deref()
+    }
+
+    /// The replacement for the resolved file `path`, trying it as is and then without its extension.
+    pub fn map_path(&self, path: &str) -> Option<&BrowserTarget> {
+        let path = posix::normalize(path);
+        if let Some(target) = self.paths.get(&path) {
+            return Some(target);
+        }
+        let ext = posix::extname(&path);
+        if ext.is_empty() {
+            return None;
+        }
+        self.paths.get(&path[..path.len() - ext.len()])
+    }
+
+    /// The replacement for the bare specifier `specifier`, which only matches a key spelled the same way.
+    pub fn map_module(&self, specifier: &str) -> Option<&BrowserTarget> {
+        self.modules.get(specifier)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.main.is_none() && self.paths.is_empty()
+    }
+}
+
+fn is_path(key: &str) -> bool {
+    key.starts_with('.') || key.starts_with('/')
+}
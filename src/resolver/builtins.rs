@@ -0,0 +1,241 @@
+use std::{fmt, str::FromStr};
+
+/// A Node release, compared by `major.minor.patch`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct NodeVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+}
+
+impl NodeVersion {
+    pub const fn new(major: u32, minor: u32, patch: u32) -> Self {
+        Self {
+            major,
+            minor,
+            patch,
+        }
+    }
+}
+
+/// Parses `process.version`-style strings, with or without the leading `v`. Missing minor and patch numbers are
+/// zero, so `"20"` is `20.0.0`.
+impl FromStr for NodeVersion {
+    type Err = ();
+
+    fn from_str(version: &str) -> Result<Self, ()> {
+        let version = version.strip_prefix('v').unwrap_or(version);
+        let mut parts = version.splitn(3, '.').map(str::parse::<u32>);
+        let major = parts.next().ok_or(())?.map_err(|_| ())?;
+        let minor = parts.next().transpose().map_err(|_| ())?.unwrap_or(0);
+        let patch = parts.next().transpose().map_err(|_| ())?.unwrap_or(0);
+        Ok(Self::new(major, minor, patch))
+    }
+}
+
+impl fmt::Display for NodeVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "v{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+/// A builtin module in the catalog.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Builtin {
+    /// The name without the `node:` prefix.
+    pub name: &'static str,
+    /// The first release that shipped it.
+    pub since: NodeVersion,
+    /// The release it was backported to on an older line, if any.
+    pub backported: Option<NodeVersion>,
+    /// Whether it can only be imported as `node:<name>`.
+    pub prefix_only: bool,
+}
+
+impl Builtin {
+    /// Whether `version` has this module.
+    pub fn is_in(&self, version: NodeVersion) -> bool {
+        let is_backported =
+            |backported: NodeVersion| backported.major == version.major && backported <= version;
+        self.since <= version || self.backported.is_some_and(is_backported)
+    }
+
+    const fn backported(mut self, major: u32, minor: u32) -> Self {
+        self.backported = Some(NodeVersion::new(major, minor, 0));
+        self
+    }
+}
+
+const fn builtin(name: &'static str, major: u32, minor: u32) -> Builtin {
+    Builtin {
+        name,
+        since: NodeVersion::new(major, minor, 0),
+        backported: None,
+        prefix_only: false,
+    }
+}
+
+const fn prefix_only(name: &'static str, major: u32, minor: u32) -> Builtin {
+    Builtin {
+        prefix_only: true,
+        ..builtin(name, major, minor)
+    }
+}
+
+// Sorted by name, like `module.builtinModules`.
+static CATALOG: &[Builtin] = &[
+    builtin("_http_agent", 0, 10),
+    builtin("_http_client", 0, 10),
+    builtin("_http_common", 0, 10),
+    builtin("_http_incoming", 0, 10),
+    builtin("_http_outgoing", 0, 10),
+    builtin("_http_server", 0, 10),
+    builtin("_stream_duplex", 0, 10),
+    builtin("_stream_passthrough", 0, 10),
+    builtin("_stream_readable", 0, 10),
+    builtin("_stream_transform", 0, 10),
+    builtin("_stream_wrap", 4, 0),
+    builtin("_stream_writable", 0, 10),
+    builtin("_tls_common", 0, 10),
+    builtin("_tls_wrap", 0, 10),
+    builtin("assert", 0, 10),
+    builtin("assert/strict", 15, 0),
+    builtin("async_hooks", 8, 1),
+    builtin("buffer", 0, 10),
+    builtin("child_process", 0, 10),
+    builtin("cluster", 0, 10),
+    builtin("console", 0, 10),
+    builtin("constants", 0, 10),
+    builtin("crypto", 0, 10),
+    builtin("dgram", 0, 10),
+    builtin("diagnostics_channel", 15, 1).backported(14, 17),
+    builtin("dns", 0, 10),
+    builtin("dns/promises", 15, 0),
+    builtin("domain", 0, 10),
+    builtin("events", 0, 10),
+    builtin("fs", 0, 10),
+    builtin("fs/promises", 14, 0),
+    builtin("http", 0, 10),
+    builtin("http2", 8, 4),
+    builtin("https", 0, 10),
+    builtin("inspector", 8, 0),
+    builtin("inspector/promises", 19, 0),
+    builtin("module", 0, 10),
+    builtin("net", 0, 10),
+    builtin("os", 0, 10),
+    builtin("path", 0, 10),
+    builtin("path/posix", 15, 3),
+    builtin("path/win32", 15, 3),
+    builtin("perf_hooks", 8, 5),
+    builtin("process", 0, 10),
+    builtin("punycode", 0, 10),
+    builtin("querystring", 0, 10),
+    builtin("readline", 0, 10),
+    builtin("readline/promises", 17, 0),
+    builtin("repl", 0, 10),
+    prefix_only("sea", 21, 7).backported(20, 12),
+    prefix_only("sqlite", 22, 5),
+    builtin("stream", 0, 10),
+    builtin("stream/consumers", 16, 7),
+    builtin("stream/promises", 15, 0),
+    builtin("stream/web", 16, 5),
+    builtin("string_decoder", 0, 10),
+    builtin("sys", 0, 10),
+    prefix_only("test", 18, 0).backported(16, 17),
+    prefix_only("test/reporters", 19, 9).backported(18, 17),
+    builtin("timers", 0, 10),
+    builtin("timers/promises", 15, 0),
+    builtin("tls", 0, 10),
+    builtin("trace_events", 10, 0),
+    builtin("tty", 0, 10),
+    builtin("url", 0, 10),
+    builtin("util", 0, 10),
+    builtin("util/types", 15, 3),
+    builtin("v8", 1, 0),
+    builtin("vm", 0, 10),
+    builtin("wasi", 13, 3).backported(12, 16),
+    builtin("worker_threads", 10, 5),
+    builtin("zlib", 0, 10),
+];
+
+/// The builtin modules of a Node release, to tell builtins from packages without asking Node.
+/// ```rust
+/// use nodejs_path::resolver::{BuiltinModules, NodeVersion};
+///
+/// let node16 = BuiltinModules::for_version(NodeVersion::new(16, 0, 0));
+/// assert!(node16.is_builtin("fs/promises"));
+/// assert!(!node16.is_builtin("node:test"));
+///
+/// let node20 = BuiltinModules::for_version("v20.11.1".parse().unwrap());
+/// assert!(node20.is_builtin("node:test"));
+/// // `test` is only a builtin with the prefix, so this is a package from npm.
+/// assert!(!node20.is_builtin("test"));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct BuiltinModules {
+    version: NodeVersion,
+}
+
+impl BuiltinModules {
+    pub fn for_version(version: NodeVersion) -> Self {
+        Self { version }
+    }
+
+    /// Every module in the catalog, whatever the release.
+    pub fn latest() -> Self {
+        Self::for_version(NodeVersion::new(u32::MAX, 0, 0))
+    }
+
+    pub fn version(&self) -> NodeVersion {
+        self.version
+    }
+
+    /// The builtin `specifier` names, with or without the `node:` prefix. Prefix-only modules only match with it.
+    pub fn get(&self, specifier: &str) -> Option<&'static Builtin> {
+        let (name, prefixed) = match specifier.strip_prefix("node:") {
+            Some(name) => (name, true),
+            None => (specifier, false),
+        };
+        let builtin = &CATALOG[CATALOG.binary_search_by(|b| b.name.cmp(name)).ok()?];
+        (builtin.is_in(self.version) && (prefixed || !builtin.prefix_only)).then_some(builtin)
+    }
+
+    /// Same as Node's `module.isBuiltin`.
+    pub fn is_builtin(&self, specifier: &str) -> bool {
+        self.get(specifier).is_some()
+    }
+
+    /// The modules of this release, in the order of `module.builtinModules`. Unlike that list, prefix-only modules
+    /// are included.
+    pub fn iter(&self) -> impl Iterator<Item = &'static Builtin> {
+        let version = self.version;
+        CATALOG.iter().filter(move |b| b.is_in(version))
+    }
+}
+
+impl Default for BuiltinModules {
+    fn default() -> Self {
+        Self::latest()
+    }
+}
+
+/// Whether `specifier` is a builtin module of the latest Node release, like `module.isBuiltin`.
+/// ```rust
+/// use nodejs_path::resolver::is_builtin;
+///
+/// assert!(is_builtin("fs"));
+/// assert!(is_builtin("node:fs/promises"));
+/// assert!(is_builtin("node:test"));
+/// assert!(!is_builtin("test"));
+/// assert!(!is_builtin("node:lodash"));
+/// ```
+pub fn is_builtin(specifier: &str) -> bool {
+    BuiltinModules::latest().is_builtin(specifier)
+}
+
+/// The name of the builtin `specifier` names in the latest release, without its `node:` prefix.
+pub(crate) fn builtin_name(specifier: &str) -> Option<&'static str> {
+    BuiltinModules::latest()
+        .get(specifier)
+        .map(|builtin| builtin.name)
+}
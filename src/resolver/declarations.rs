@@ -0,0 +1,331 @@
+use std::io;
+
+use serde_json::{Map, Value};
+
+use crate::posix;
+
+use super::{
+    builtins::NodeVersion,
+    extension_alias::ExtensionAlias,
+    fs::FileSystem,
+    package_json::PackageJson,
+    specifier::{package_name_len, parse_specifier, Specifier},
+};
+
+/// Finds the `.d.ts` files of packages like TypeScript's `node10` resolution does for declarations.
+///
+/// A package is looked up in each `node_modules` from the importing directory upwards, trying the package itself
+/// and then its `@types` package in every directory before moving up. Inside a package, the entry is the first of
+/// `typings`, `types`, `main` with its extension swapped and `index.d.ts`, and a subpath is a file or a directory
+/// with an `index.d.ts`. When `typesVersions` has a range matching the TypeScript version, its path map is tried
+/// first, with `*` standing for the rest of the path.
+/// ```rust
+/// use nodejs_path::resolver::{DeclarationResolver, MemoryFileSystem};
+///
+/// let mut fs = MemoryFileSystem::new();
+/// fs.add_file("/app/node_modules/typed/package.json", r#"{ "types": "./dist/index" }"#)
+///     .add_file("/app/node_modules/typed/dist/index.d.ts", "")
+///     .add_file(
+///         "/app/node_modules/versioned/package.json",
+///         r#"{ "typesVersions": { ">=4.2": { "*": ["ts4.2/*"] } } }"#,
+///     )
+///     .add_file("/app/node_modules/versioned/ts4.2/sub.d.ts", "")
+///     .add_file("/app/node_modules/@babel/core/index.js", "")
+///     .add_file("/app/node_modules/@types/babel__core/index.d.ts", "");
+///
+/// let resolver = DeclarationResolver::new(&fs);
+/// let resolve = |specifier| resolver.resolve(specifier, "/app/src").unwrap();
+/// assert_eq!(resolve("typed").as_deref(), Some("/app/node_modules/typed/dist/index.d.ts"));
+/// assert_eq!(resolve("versioned/sub").as_deref(), Some("/app/node_modules/versioned/ts4.2/sub.d.ts"));
+/// assert_eq!(resolve("@babel/core").as_deref(), Some("/app/node_modules/@types/babel__core/index.d.ts"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct DeclarationResolver<F> {
+    fs: F,
+    typescript_version: NodeVersion,
+}
+
+impl<F: FileSystem> DeclarationResolver<F> {
+    pub fn new(fs: F) -> Self {
+        Self {
+            fs,
+            typescript_version: NodeVersion::new(5, 4, 0),
+        }
+    }
+
+    /// The TypeScript version `typesVersions` ranges are matched against. Defaults to 5.4.
+    pub fn typescript_version(mut self, major: u32, minor: u32) -> Self {
+        self.typescript_version = NodeVersion::new(major, minor, 0);
+        self
+    }
+
+    /// The declaration file for the bare specifier `specifier` imported from the directory `from_dir`. Relative,
+    /// absolute, `node:` and URL specifiers, and ones [`parse_specifier`] rejects, aren't looked up and give `None`.
+    /// A `package.json` that isn't valid JSON is an [`io::ErrorKind::InvalidData`] error.
+    pub fn resolve(&self, specifier: &str, from_dir: &str) -> io::Result<Option<String>> {
+        let (name, subpath) = match parse_specifier(specifier) {
+            Ok(Specifier::Bare { name, subpath }) => (name, subpath),
+            // TypeScript has no builtins, so a name like `events` is looked up like any other package.
+            Ok(Specifier::Builtin(_)) if !specifier.starts_with("node:") => {
+                specifier.split_at(package_name_len(specifier))
+            }
+            _ => return Ok(None),
+        };
+        let subpath = subpath.trim_start_matches('/');
+        let types_name = types_package_name(name);
+        let packages = if name.starts_with("@types/") {
+            vec![name]
+        } else {
+            vec![name, types_name.as_str()]
+        };
+
+        let mut dir = posix::resolve_impl(&[from_dir]);
+        loop {
+            if posix::basename_impl(&dir) != "node_modules" {
+                for package in &packages {
+                    let package_dir = posix::join_impl(&[dir.as_str(), "node_modules", package]);
+                    if !self.fs.is_dir(&package_dir) {
+                        continue;
+                    }
+                    if let Some(found) = self.resolve_package(&package_dir, subpath)? {
+                        return Ok(Some(found));
+                    }
+                }
+            }
+            let parent = posix::dirname(&dir);
+            if parent == dir {
+                return Ok(None);
+            }
+            dir = parent;
+        }
+    }
+
+    /// The declaration file for `subpath`, without a leading `/`, in the package directory `package_dir`. An empty
+    /// subpath is the package entry.
+    pub fn resolve_package(&self, package_dir: &str, subpath: &str) -> io::Result<Option<String>> {
+        let package_json = PackageJson::read(&self.fs, package_dir)?;
+        let paths = package_json
+            .as_ref()
+            .and_then(|package_json| self.types_versions_paths(package_json));
+        if !subpath.is_empty() {
+            let mapped = paths.and_then(|paths| self.map_paths(package_dir, paths, subpath));
+            return Ok(
+                mapped.or_else(|| self.file_or_dir(&posix::join_impl(&[package_dir, subpath])))
+            );
+        }
+
+        let types = package_json.as_ref().and_then(|package_json| {
+            package_json
+                .str_field("typings")
+                .or_else(|| package_json.str_field("types"))
+        });
+        if let Some(paths) = paths {
+            let entry = posix::normalize(types.unwrap_or("index"));
+            if let Some(found) = self.map_paths(package_dir, paths, &entry) {
+                return Ok(Some(found));
+            }
+        }
+        if let Some(found) =
+            types.and_then(|types| self.file_or_dir(&posix::join_impl(&[package_dir, types])))
+        {
+            return Ok(Some(found));
+        }
+        let main = package_json.as_ref().and_then(PackageJson::main);
+        if let Some(found) =
+            main.and_then(|main| self.file_or_dir(&posix::join_impl(&[package_dir, main])))
+        {
+            return Ok(Some(found));
+        }
+        Ok(self.index(package_dir))
+    }
+
+    /// The path map of the first `typesVersions` range the TypeScript version satisfies.
+    fn types_versions_paths<'p>(
+        &self,
+        package_json: &'p PackageJson,
+    ) -> Option<&'p Map<String, Value>> {
+        let types_versions = package_json.field("typesVersions")?.as_object()?;
+        types_versions
+            .iter()
+            .find(|(range, _)| range_matches(range, self.typescript_version))
+            .and_then(|(_, paths)| paths.as_object())
+    }
+
+    /// Tries the targets of the key `request` matches best: itself, or else the pattern with the longest prefix.
+    fn map_paths(
+        &self,
+        package_dir: &str,
+        paths: &Map<String, Value>,
+        request: &str,
+    ) -> Option<String> {
+        let (targets, star) = match paths.get(request) {
+            Some(targets) => (targets, ""),
+            None => paths
+                .iter()
+                .filter_map(|(pattern, targets)| {
+                    let (prefix, suffix) = pattern.split_once('*')?;
+                    if suffix.contains('*') {
+                        return None;
+                    }
+                    let star = request.strip_prefix(prefix)?.strip_suffix(suffix)?;
+                    Some((prefix.len(), targets, star))
+                })
+                .max_by_key(|(prefix_len, ..)| *prefix_len)
+                .map(|(_, targets, star)| (targets, star))?,
+        };
+        targets
+            .as_array()?
+            .iter()
+            .filter_map(Value::as_str)
+            .find_map(|target| {
+                self.file_or_dir(&posix::join_impl(&[
+                    package_dir,
+                    &target.replacen('*', star, 1),
+                ]))
+            })
+    }
+
+    fn file_or_dir(&self, path: &str) -> Option<String> {
+        self.declaration_file(path).or_else(|| self.index(path))
+    }
+
+    /// `path` if it is a declaration file already, and otherwise its declaration file: `.js` and `.ts` sources swap
+    /// their extension for `.d.ts`, `.mjs` and `.mts` for `.d.mts`, `.cjs` and `.cts` for `.d.cts`, and any other
+    /// path gets `.d.ts` appended.
+    fn declaration_file(&self, path: &str) -> Option<String> {
+        if path.ends_with('/') {
+            return None;
+        }
+        if [".d.ts", ".d.mts", ".d.cts"]
+            .iter()
+            .any(|ext| path.ends_with(ext))
+        {
+            return self.fs.is_file(path).then(|| path.to_owned());
+        }
+        let alias = ExtensionAlias::new()
+            .alias(".js", [".d.ts"])
+            .alias(".jsx", [".d.ts"])
+            .alias(".ts", [".d.ts"])
+            .alias(".tsx", [".d.ts"])
+            .alias(".mjs", [".d.mts"])
+            .alias(".mts", [".d.mts"])
+            .alias(".cjs", [".d.cts"])
+            .alias(".cts", [".d.cts"]);
+        let mut candidates = alias.candidates(path);
+        if candidates == [path] {
+            candidates = vec![format!("{}.d.ts", path)];
+        }
+        candidates
+            .into_iter()
+            .find(|candidate| self.fs.is_file(candidate))
+    }
+
+    /// The declaration entry of the directory `dir`: its own `package.json` `typings` or `types`, or `index.d.ts`.
+    fn index(&self, dir: &str) -> Option<String> {
+        if !self.fs.is_dir(dir) {
+            return None;
+        }
+        let package_json = PackageJson::read(&self.fs, dir).ok().flatten();
+        let types = package_json.as_ref().and_then(|package_json| {
+            package_json
+                .str_field("typings")
+                .or_else(|| package_json.str_field("types"))
+        });
+        if let Some(found) =
+            types.and_then(|types| self.declaration_file(&posix::join_impl(&[dir, types])))
+        {
+            return Some(found);
+        }
+        let index = posix::join_impl(&[dir, "index.d.ts"]);
+        self.fs.is_file(&index).then_some(index)
+    }
+}
+
+/// The `@types` package holding the declarations of `name`. Scoped names are mangled, so `@babel/core` becomes
+/// `@types/babel__core`.
+/// ```rust
+/// use nodejs_path::resolver::types_package_name;
+///
+/// assert_eq!(types_package_name("lodash"), "@types/lodash");
+/// assert_eq!(types_package_name("@babel/core"), "@types/babel__core");
+/// ```
+pub fn types_package_name(name: &str) -> String {
+    match name
+        .strip_prefix('@')
+        .and_then(|scoped| scoped.split_once('/'))
+    {
+        Some((scope, name)) => format!("@types/{}__{}", scope, name),
+        None => format!("@types/{}", name),
+    }
+}
+
+/// Whether `version` satisfies the semver range `range`, as far as `typesVersions` uses them: comparators joined by
+/// spaces, alternatives joined by `||`, `^` and `~` ranges, and partial or x-ranges like `4.2` and `4.x`.
+fn range_matches(range: &str, version: NodeVersion) -> bool {
+    range.split("||").any(|alternative| {
+        alternative.split_whitespace().all(|comparator| {
+            let (op, bound) = [">=", "<=", ">", "<", "=", "^", "~"]
+                .iter()
+                .find_map(|op| comparator.strip_prefix(op).map(|bound| (*op, bound)))
+                .unwrap_or(("", comparator));
+            let parts = match parse_partial(bound) {
+                Some(parts) => parts,
+                None => return false,
+            };
+            let lower = NodeVersion::new(
+                parts[0].unwrap_or(0),
+                parts[1].unwrap_or(0),
+                parts[2].unwrap_or(0),
+            );
+            // The first version past the parts given, so `4.2` stands for `>=4.2.0 <4.3.0`. `None` for `*`.
+            let given = parts.iter().take_while(|part| part.is_some()).count();
+            let upper = given.checked_sub(1).map(|last| bump(lower, last));
+            match op {
+                ">=" => version >= lower,
+                "<" => upper.is_some() && version < lower,
+                ">" if given == 3 => version > lower,
+                ">" => upper.is_some_and(|upper| version >= upper),
+                "<=" if given == 3 => version <= lower,
+                "<=" => upper.is_none_or(|upper| version < upper),
+                "^" => {
+                    // The leftmost non-zero part given is the one that may not change.
+                    let fixed = (0..given.saturating_sub(1))
+                        .find(|&i| parts[i] != Some(0))
+                        .unwrap_or(given.saturating_sub(1));
+                    version >= lower && (given == 0 || version < bump(lower, fixed))
+                }
+                "~" => version >= lower && (given == 0 || version < bump(lower, given.min(2) - 1)),
+                _ => version >= lower && upper.is_none_or(|upper| version < upper),
+            }
+        })
+    })
+}
+
+/// The parts of a version like `4.2.1`, `4.2` or `4.x`. Parts after a missing or `x`, `X` or `*` one are `None`.
+fn parse_partial(version: &str) -> Option<[Option<u32>; 3]> {
+    let version = version.strip_prefix('v').unwrap_or(version);
+    let mut parts = [None; 3];
+    for (i, part) in version.split('.').enumerate() {
+        if i == 3 {
+            return None;
+        }
+        if matches!(part, "x" | "X" | "*") {
+            break;
+        }
+        parts[i] = Some(part.parse().ok()?);
+    }
+    Some(parts)
+}
+
+/// `version` with the part at `index` incremented and the parts after it zeroed.
+fn bump(version: NodeVersion, index: usize) -> NodeVersion {
+    match index {
+        0 => NodeVersion::new(version.major.saturating_add(1), 0, 0),
+        1 => NodeVersion::new(version.major, version.minor.saturating_add(1), 0),
+        _ => NodeVersion::new(
+            version.major,
+            version.minor,
+            version.patch.saturating_add(1),
+        ),
+    }
+}
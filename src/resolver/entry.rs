@@ -0,0 +1,136 @@
+use std::io;
+
+use crate::posix;
+
+use super::{fs::FileSystem, package_json::PackageJson};
+
+/// The entry point [`EntryResolver`] picked for a package.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct PackageEntry {
+    pub path: String,
+    /// The `package.json` field it came from, or `None` for a main file found in the package directory.
+    pub field: Option<String>,
+}
+
+/// Picks the entry point of a package directory the way bundlers do: the first field of `main_fields` naming a
+/// file, tried as is, with each of `extensions` and as a directory of `main_files`, and then the `main_files` of the
+/// package directory itself.
+///
+/// The defaults match `require`: the `main` field, then `index` with `.js`, `.json` or `.node`. Fields that aren't
+/// strings are skipped, so an object `browser` field falls through to the next one.
+/// ```rust
+/// use nodejs_path::resolver::{EntryResolver, MemoryFileSystem};
+///
+/// let mut fs = MemoryFileSystem::new();
+/// fs.add_file(
+///     "/app/node_modules/pkg/package.json",
+///     r#"{ "main": "./lib/main", "module": "./esm/index.mjs" }"#,
+/// )
+/// .add_file("/app/node_modules/pkg/lib/main.js", "")
+/// .add_file("/app/node_modules/pkg/esm/index.mjs", "");
+///
+/// let node = EntryResolver::new(&fs);
+/// let entry = node.resolve("/app/node_modules/pkg").unwrap().unwrap();
+/// assert_eq!(entry.path, "/app/node_modules/pkg/lib/main.js");
+///
+/// let bundler = EntryResolver::new(&fs).main_fields(["browser", "module", "main"]);
+/// let entry = bundler.resolve("/app/node_modules/pkg").unwrap().unwrap();
+/// assert_eq!(entry.path, "/app/node_modules/pkg/esm/index.mjs");
+/// assert_eq!(entry.field.as_deref(), Some("module"));
+/// ```
+#[derive(Debug, Clone)]
+pub struct EntryResolver<F> {
+    fs: F,
+    main_fields: Vec<String>,
+    main_files: Vec<String>,
+    extensions: Vec<String>,
+}
+
+impl<F: FileSystem> EntryResolver<F> {
+    pub fn new(fs: F) -> Self {
+        Self {
+            fs,
+            main_fields: vec!["main".to_owned()],
+            main_files: vec!["index".to_owned()],
+            extensions: [".js", ".json", ".node"].map(str::to_owned).to_vec(),
+        }
+    }
+
+    /// The `package.json` fields to try, in order. Replaces `["main"]`.
+    pub fn main_fields<I, S>(mut self, fields: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.main_fields = fields.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The file names, without extension, to look for in a directory. Replaces `["index"]`.
+    pub fn main_files<I, S>(mut self, files: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.main_files = files.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The extensions to append, in order, with their leading `.`. Replaces `[".js", ".json", ".node"]`.
+    pub fn extensions<I, S>(mut self, extensions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.extensions = extensions.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// The entry point of the package in the absolute directory `package_dir`, or `None` if no candidate is a file.
+    /// A missing `package.json` only skips the fields, while one that isn't valid JSON is an
+    /// [`io::ErrorKind::InvalidData`] error.
+    pub fn resolve(&self, package_dir: &str) -> io::Result<Option<PackageEntry>> {
+        if let Some(package_json) = PackageJson::read(&self.fs, package_dir)? {
+            for field in &self.main_fields {
+                let main = match package_json.str_field(field) {
+                    Some(main) if !main.is_empty() => main,
+                    _ => continue,
+                };
+                let main = posix::join_impl(&[package_dir, main]);
+                if let Some(path) = self.file(&main).or_else(|| self.main_file(&main)) {
+                    return Ok(Some(PackageEntry {
+                        path,
+                        field: Some(field.clone()),
+                    }));
+                }
+            }
+        }
+        Ok(self
+            .main_file(package_dir)
+            .map(|path| PackageEntry { path, field: None }))
+    }
+
+    /// `path` itself, or `path` with one of the extensions.
+    fn file(&self, path: &str) -> Option<String> {
+        if self.fs.is_file(path) {
+            return Some(path.to_owned());
+        }
+        if path.ends_with('/') {
+            return None;
+        }
+        self.extensions
+            .iter()
+            .map(|ext| format!("{}{}", path, ext))
+            .find(|candidate| self.fs.is_file(candidate))
+    }
+
+    /// The first main file with one of the extensions in the directory `dir`.
+    fn main_file(&self, dir: &str) -> Option<String> {
+        self.main_files.iter().find_map(|main_file| {
+            self.extensions
+                .iter()
+                .map(|ext| posix::join_impl(&[dir, &format!("{}{}", main_file, ext)]))
+                .find(|candidate| self.fs.is_file(candidate))
+        })
+    }
+}
@@ -0,0 +1,879 @@
+use std::{cmp::Ordering, error, fmt};
+
+use serde_json::{Map, Value};
+
+use crate::posix;
+
+use super::{
+    builtins::BuiltinModules,
+    fs::{FileKind, FileSystem},
+    package_json::PackageJson,
+    specifier::{package_name_len, url_scheme},
+    url::{decode_pathname, encode_pathname, parse_file_url, resolve_pathname, split_suffix},
+};
+
+/// Conditions Node matches in `exports` and `imports` when resolving an `import`. `node-addons` goes away with
+/// `--no-addons`.
+const DEFAULT_CONDITIONS: &[&str] = &["node", "import", "node-addons"];
+
+/// Why [`EsmResolver::resolve`] failed. [`EsmError::code`] is the `code` Node's error would have, and `Display`
+/// follows Node's message. `base` is the path of the importing module.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EsmError {
+    /// The specifier, or the URL it resolved to, is malformed.
+    InvalidModuleSpecifier {
+        request: String,
+        reason: String,
+        base: String,
+    },
+    /// A `package.json` is not valid JSON, or its `exports` or `imports` are malformed.
+    InvalidPackageConfig {
+        path: String,
+        reason: String,
+        base: String,
+    },
+    /// An `exports` or `imports` target points outside its package, or is not a string, object, array or `null`.
+    InvalidPackageTarget {
+        package_json: String,
+        key: String,
+        /// The target as JSON.
+        target: String,
+        is_imports: bool,
+        base: String,
+    },
+    /// The package has `exports`, but they don't expose `subpath`.
+    PackagePathNotExported {
+        package_json: String,
+        subpath: String,
+        base: String,
+    },
+    /// The `#` specifier is not in the `imports` of the importing package, or there is no package.
+    PackageImportNotDefined {
+        specifier: String,
+        package_json: Option<String>,
+        base: String,
+    },
+    /// The specifier resolved to a directory. ES modules need the full path of the file.
+    UnsupportedDirImport { path: String, base: String },
+    /// The specifier resolved to a path where there is no file.
+    ModuleNotFound { path: String, base: String },
+    /// No `node_modules` directory has the package, or it has neither a `main` nor an `index.js`.
+    PackageNotFound { package: String, base: String },
+    /// A URL specifier whose scheme is not `file:`, `data:` or `node:`.
+    UnsupportedUrlScheme { url: String },
+    /// A `node:` specifier naming no builtin module.
+    UnknownBuiltinModule { specifier: String },
+    /// The parent URL is not a `file:` URL, or a URL has no valid path.
+    InvalidUrl { url: String },
+}
+
+impl EsmError {
+    pub fn code(&self) -> &'static str {
+        match self {
+            EsmError::InvalidModuleSpecifier { .. } => "ERR_INVALID_MODULE_SPECIFIER",
+            EsmError::InvalidPackageConfig { .. } => "ERR_INVALID_PACKAGE_CONFIG",
+            EsmError::InvalidPackageTarget { .. } => "ERR_INVALID_PACKAGE_TARGET",
+            EsmError::PackagePathNotExported { .. } => "ERR_PACKAGE_PATH_NOT_EXPORTED",
+            EsmError::PackageImportNotDefined { .. } => "ERR_PACKAGE_IMPORT_NOT_DEFINED",
+            EsmError::UnsupportedDirImport { .. } => "ERR_UNSUPPORTED_DIR_IMPORT",
+            EsmError::ModuleNotFound { .. } | EsmError::PackageNotFound { .. } => {
+                "ERR_MODULE_NOT_FOUND"
+            }
+            EsmError::UnsupportedUrlScheme { .. } => "ERR_UNSUPPORTED_ESM_URL_SCHEME",
+            EsmError::UnknownBuiltinModule { .. } => "ERR_UNKNOWN_BUILTIN_MODULE",
+            EsmError::InvalidUrl { .. } => "ERR_INVALID_URL",
+        }
+    }
+}
+
+impl fmt::Display for EsmError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EsmError::InvalidModuleSpecifier {
+                request,
+                reason,
+                base,
+            } => write!(
+                f,
+                "Invalid module \"{}\" {} imported from {}",
+                request, reason, base
+            ),
+            EsmError::InvalidPackageConfig { path, reason, base } => write!(
+                f,
+                "Invalid package config {} while importing {}. {}",
+                path, base, reason
+            ),
+            EsmError::InvalidPackageTarget {
+                package_json,
+                key,
+                target,
+                is_imports,
+                base,
+            } => {
+                let field = if *is_imports { "imports" } else { "exports" };
+                if key == "." {
+                    write!(f, "Invalid \"{}\" main target {}", field, target)?;
+                } else {
+                    write!(
+                        f,
+                        "Invalid \"{}\" target {} defined for '{}'",
+                        field, target, key
+                    )?;
+                }
+                write!(
+                    f,
+                    " in the package config {} imported from {}",
+                    package_json, base
+                )?;
+                let is_relative = target.starts_with("\"./") || target == "\"\"";
+                if !is_imports && target.starts_with('"') && !is_relative {
+                    write!(f, "; targets must start with \"./\"")?;
+                }
+                Ok(())
+            }
+            EsmError::PackagePathNotExported {
+                package_json,
+                subpath,
+                base,
+            } if subpath == "." => write!(
+                f,
+                "No \"exports\" main defined in {} imported from {}",
+                package_json, base
+            ),
+            EsmError::PackagePathNotExported {
+                package_json,
+                subpath,
+                base,
+            } => write!(
+                f,
+                "Package subpath '{}' is not defined by \"exports\" in {} imported from {}",
+                subpath, package_json, base
+            ),
+            EsmError::PackageImportNotDefined {
+                specifier,
+                package_json,
+                base,
+            } => {
+                write!(
+                    f,
+                    "Package import specifier \"{}\" is not defined",
+                    specifier
+                )?;
+                if let Some(package_json) = package_json {
+                    write!(f, " in package {}", package_json)?;
+                }
+                write!(f, " imported from {}", base)
+            }
+            EsmError::UnsupportedDirImport { path, base } => write!(
+                f,
+                "Directory import '{}' is not supported resolving ES modules imported from {}",
+                path, base
+            ),
+            EsmError::ModuleNotFound { path, base } => {
+                write!(f, "Cannot find module '{}' imported from {}", path, base)
+            }
+            EsmError::PackageNotFound { package, base } => {
+                write!(
+                    f,
+                    "Cannot find package '{}' imported from {}",
+                    package, base
+                )
+            }
+            EsmError::UnsupportedUrlScheme { url } => {
+                let scheme = url_scheme(url).unwrap_or(url);
+                write!(
+                    f,
+                    "Only URLs with a scheme in: file, data, and node are supported "
+                )?;
+                write!(
+                    f,
+                    "by the default ESM loader. Received protocol '{}:'",
+                    scheme
+                )
+            }
+            EsmError::UnknownBuiltinModule { specifier } => {
+                write!(f, "No such built-in module: {}", specifier)
+            }
+            EsmError::InvalidUrl { url } => write!(f, "Invalid URL: {}", url),
+        }
+    }
+}
+
+impl error::Error for EsmError {}
+
+/// Resolves `import` specifiers like Node's ESM loader, following the `ESM_RESOLVE` algorithm of its docs.
+///
+/// Relative and absolute specifiers are resolved with [`posix::resolve_impl`] semantics and need the full file name,
+/// bare specifiers are looked up in `node_modules` honoring `exports`, and `#` specifiers in the `imports` of the
+/// importing package. The result is a `file:` URL of the real path, or a `node:` or `data:` URL.
+/// ```rust
+/// use nodejs_path::resolver::{EsmResolver, MemoryFileSystem};
+///
+/// let mut fs = MemoryFileSystem::new();
+/// fs.add_file("/app/src/util.js", "")
+///     .add_file("/app/node_modules/dep/package.json", r#"{ "exports": { "import": "./dep.mjs" } }"#)
+///     .add_file("/app/node_modules/dep/dep.mjs", "");
+///
+/// let resolver = EsmResolver::new(&fs);
+/// let parent = "file:///app/src/main.js";
+/// assert_eq!(resolver.resolve("./util.js", parent).unwrap(), "file:///app/src/util.js");
+/// assert_eq!(resolver.resolve("dep", parent).unwrap(), "file:///app/node_modules/dep/dep.mjs");
+/// assert_eq!(resolver.resolve("fs", parent).unwrap(), "node:fs");
+/// assert_eq!(resolver.resolve("./util", parent).unwrap_err().code(), "ERR_MODULE_NOT_FOUND");
+/// ```
+#[derive(Debug, Clone)]
+pub struct EsmResolver<F> {
+    fs: F,
+    conditions: Vec<String>,
+    preserve_symlinks: bool,
+    builtins: BuiltinModules,
+}
+
+impl<F: FileSystem> EsmResolver<F> {
+    pub fn new(fs: F) -> Self {
+        Self {
+            fs,
+            conditions: DEFAULT_CONDITIONS.iter().map(|c| c.to_string()).collect(),
+            preserve_symlinks: false,
+            builtins: BuiltinModules::latest(),
+        }
+    }
+
+    /// Also matches `conditions` in `exports` and `imports`, like `--conditions`.
+    pub fn with_conditions<I, S>(mut self, conditions: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.conditions
+            .extend(conditions.into_iter().map(Into::into));
+        self
+    }
+
+    /// Resolves builtins as a given Node release has them, instead of the latest catalog.
+    pub fn with_builtins(mut self, builtins: BuiltinModules) -> Self {
+        self.builtins = builtins;
+        self
+    }
+
+    /// Whether the `node-addons` condition matches. `false` is the same as `--no-addons`.
+    pub fn addons(mut self, addons: bool) -> Self {
+        self.conditions
+            .retain(|condition| condition != "node-addons");
+        if addons {
+            self.conditions.push("node-addons".to_owned());
+        }
+        self
+    }
+
+    /// Keeps symlinks in resolved URLs instead of returning real paths, like `--preserve-symlinks`.
+    pub fn preserve_symlinks(mut self, preserve_symlinks: bool) -> Self {
+        self.preserve_symlinks = preserve_symlinks;
+        self
+    }
+
+    /// Resolves `specifier` imported from the module at the `file:` URL `parent_url`.
+    pub fn resolve(&self, specifier: &str, parent_url: &str) -> Result<String, EsmError> {
+        let invalid_parent = || EsmError::InvalidUrl {
+            url: parent_url.to_owned(),
+        };
+        let (parent, _) = parse_file_url(parent_url).ok_or_else(invalid_parent)?;
+        let base = decode_pathname(&parent).ok_or_else(invalid_parent)?;
+        Resolution {
+            resolver: self,
+            base,
+        }
+        .resolve(specifier, &parent)
+    }
+}
+
+/// Where a specifier leads before the file checks: an encoded `file:` URL pathname, or another URL.
+enum Resolved {
+    Pathname(String),
+    Url(String),
+}
+
+/// The outcome of `PACKAGE_TARGET_RESOLVE` short of an error. `Excluded` is Node's `null`, which ends a condition
+/// search, `Unmatched` its `undefined`, which moves on to the next condition.
+enum Target {
+    Resolved(Resolved),
+    Excluded,
+    Unmatched,
+}
+
+/// One call to [`EsmResolver::resolve`]. `base` is the decoded parent path that errors report.
+struct Resolution<'a, F> {
+    resolver: &'a EsmResolver<F>,
+    base: String,
+}
+
+impl<'a, F: FileSystem> Resolution<'a, F> {
+    fn resolve(&self, specifier: &str, parent: &str) -> Result<String, EsmError> {
+        let (resolved, suffix) = if let Some(scheme) = url_scheme(specifier) {
+            match scheme.to_ascii_lowercase().as_str() {
+                "file" => {
+                    let (pathname, suffix) =
+                        parse_file_url(specifier).ok_or_else(|| EsmError::InvalidUrl {
+                            url: specifier.to_owned(),
+                        })?;
+                    (Resolved::Pathname(pathname), suffix)
+                }
+                "node" => {
+                    // Builtins only match the lowercase prefix, so `NODE:fs` is unknown like in Node's loader.
+                    return if self.resolver.builtins.is_builtin(specifier) {
+                        Ok(specifier.to_owned())
+                    } else {
+                        Err(EsmError::UnknownBuiltinModule {
+                            specifier: specifier.to_owned(),
+                        })
+                    };
+                }
+                "data" => return Ok(specifier.to_owned()),
+                _ => {
+                    return Err(EsmError::UnsupportedUrlScheme {
+                        url: specifier.to_owned(),
+                    })
+                }
+            }
+        } else if is_relative_or_absolute(specifier) {
+            let (path, suffix) = split_suffix(specifier);
+            (
+                Resolved::Pathname(resolve_pathname(dir_of(parent), path)),
+                suffix,
+            )
+        } else if specifier.starts_with('#') {
+            (self.package_imports_resolve(specifier, parent)?, "")
+        } else {
+            (self.package_resolve(specifier, parent)?, "")
+        };
+
+        match resolved {
+            Resolved::Url(url) => Ok(url),
+            Resolved::Pathname(pathname) => self.finalize(&pathname, suffix),
+        }
+    }
+
+    /// Checks that a file is there and returns the URL of its real path.
+    fn finalize(&self, pathname: &str, suffix: &str) -> Result<String, EsmError> {
+        let lowercase = pathname.to_ascii_lowercase();
+        if lowercase.contains("%2f") || lowercase.contains("%5c") {
+            return Err(EsmError::InvalidModuleSpecifier {
+                request: pathname.to_owned(),
+                reason: "must not include encoded \"/\" or \"\\\" characters".to_owned(),
+                base: self.base.clone(),
+            });
+        }
+
+        let path = self.path_of(pathname)?;
+        let file = match path.strip_suffix(posix::sep) {
+            Some(file) if !file.is_empty() => file,
+            _ => &path,
+        };
+        let not_found = || EsmError::ModuleNotFound {
+            path: path.clone(),
+            base: self.base.clone(),
+        };
+        match self.resolver.fs.kind(file) {
+            Some(FileKind::File) => {}
+            Some(FileKind::Dir) => {
+                return Err(EsmError::UnsupportedDirImport {
+                    path: path.clone(),
+                    base: self.base.clone(),
+                })
+            }
+            None => return Err(not_found()),
+        }
+
+        if self.resolver.preserve_symlinks {
+            return Ok(format!("file://{}{}", pathname, suffix));
+        }
+        let real = self
+            .resolver
+            .fs
+            .canonicalize(file)
+            .map_err(|_| not_found())?;
+        Ok(format!("file://{}{}", encode_pathname(&real), suffix))
+    }
+
+    /// `PACKAGE_RESOLVE`: a bare specifier, looked up in the `node_modules` directories above `parent`.
+    fn package_resolve(&self, specifier: &str, parent: &str) -> Result<Resolved, EsmError> {
+        if self.resolver.builtins.is_builtin(specifier) {
+            return Ok(Resolved::Url(format!("node:{}", specifier)));
+        }
+
+        let invalid = || EsmError::InvalidModuleSpecifier {
+            request: specifier.to_owned(),
+            reason: "is not a valid package name".to_owned(),
+            base: self.base.clone(),
+        };
+        let name_len = package_name_len(specifier);
+        let name = &specifier[..name_len];
+        // Node only rejects what would break the lookup, unlike `validate_package_name`.
+        let is_scoped_without_name = name.starts_with('@') && !name.contains('/');
+        if name.is_empty()
+            || is_scoped_without_name
+            || name.starts_with('.')
+            || name.contains('\\')
+            || name.contains('%')
+        {
+            return Err(invalid());
+        }
+        let subpath = format!(".{}", &specifier[name_len..]);
+
+        if let Some(resolved) = self.package_self_resolve(name, &subpath, parent)? {
+            return Ok(resolved);
+        }
+
+        let mut dir = dir_of(parent);
+        loop {
+            let package = format!("{}node_modules/{}/", dir, name);
+            if self.resolver.fs.is_dir(&self.path_of(&package)?) {
+                let package_json = self.read_package_json(&package)?;
+                if let Some(exports) = package_json.as_ref().and_then(PackageJson::exports) {
+                    return self.package_exports_resolve(&package, &subpath, exports);
+                }
+                if subpath == "." {
+                    return self.legacy_main_resolve(&package, package_json.as_ref());
+                }
+                return Ok(Resolved::Pathname(resolve_pathname(&package, &subpath)));
+            }
+            if dir == "/" {
+                break;
+            }
+            dir = dir_of(&dir[..dir.len() - 1]);
+        }
+
+        Err(EsmError::PackageNotFound {
+            package: name.to_owned(),
+            base: self.base.clone(),
+        })
+    }
+
+    /// `PACKAGE_SELF_RESOLVE`: a package importing itself by name through its own `exports`.
+    fn package_self_resolve(
+        &self,
+        name: &str,
+        subpath: &str,
+        parent: &str,
+    ) -> Result<Option<Resolved>, EsmError> {
+        let (scope, package_json) = match self.lookup_package_scope(parent)? {
+            Some(found) => found,
+            None => return Ok(None),
+        };
+        match package_json.exports() {
+            Some(exports) if package_json.name() == Some(name) => self
+                .package_exports_resolve(scope, subpath, exports)
+                .map(Some),
+            _ => Ok(None),
+        }
+    }
+
+    /// The `main` of a package without `exports`, trying the extensions and index files Node still tries there.
+    fn legacy_main_resolve(
+        &self,
+        package: &str,
+        package_json: Option<&PackageJson>,
+    ) -> Result<Resolved, EsmError> {
+        let main = package_json.and_then(PackageJson::main);
+        let main_candidates = main.into_iter().flat_map(|main| {
+            [
+                "",
+                ".js",
+                ".json",
+                ".node",
+                "/index.js",
+                "/index.json",
+                "/index.node",
+            ]
+            .iter()
+            .map(move |suffix| format!("./{}{}", main, suffix))
+        });
+        let index_candidates = ["./index.js", "./index.json", "./index.node"]
+            .iter()
+            .map(|index| index.to_string());
+        for candidate in main_candidates.chain(index_candidates) {
+            let pathname = resolve_pathname(package, &candidate);
+            if self.resolver.fs.is_file(&self.path_of(&pathname)?) {
+                return Ok(Resolved::Pathname(pathname));
+            }
+        }
+        Err(EsmError::PackageNotFound {
+            package: self.path_of(package)?,
+            base: self.base.clone(),
+        })
+    }
+
+    /// `PACKAGE_EXPORTS_RESOLVE`
+    fn package_exports_resolve(
+        &self,
+        package: &str,
+        subpath: &str,
+        exports: &Value,
+    ) -> Result<Resolved, EsmError> {
+        let sugar;
+        let exports = if self.is_conditional_sugar(package, exports)? {
+            sugar = Map::from_iter([(".".to_owned(), exports.clone())]);
+            Some(&sugar)
+        } else {
+            exports.as_object()
+        };
+        if let Some(exports) = exports {
+            if let Target::Resolved(resolved) =
+                self.imports_exports_resolve(package, subpath, exports, false)?
+            {
+                return Ok(resolved);
+            }
+        }
+        Err(EsmError::PackagePathNotExported {
+            package_json: self.package_json_path(package)?,
+            subpath: subpath.to_owned(),
+            base: self.base.clone(),
+        })
+    }
+
+    /// Whether `exports` only describes the main entry, as a target or as conditions.
+    fn is_conditional_sugar(&self, package: &str, exports: &Value) -> Result<bool, EsmError> {
+        let keys = match exports {
+            Value::String(_) | Value::Array(_) => return Ok(true),
+            Value::Object(exports) => exports.keys(),
+            _ => return Ok(false),
+        };
+        let mut sugar = None;
+        for key in keys {
+            let is_condition = !key.starts_with('.');
+            match sugar {
+                None => sugar = Some(is_condition),
+                Some(sugar) if sugar != is_condition => {
+                    return Err(self.invalid_package_config(
+                        package,
+                        "\"exports\" cannot contain some keys starting with '.' and some not. The exports \
+                         object must either be an object of package subpath keys or an object of main entry \
+                         condition name keys only.",
+                    ))
+                }
+                Some(_) => {}
+            }
+        }
+        Ok(sugar.unwrap_or(false))
+    }
+
+    /// `PACKAGE_IMPORTS_RESOLVE`: a `#` specifier, looked up in the `imports` of the package containing `parent`.
+    fn package_imports_resolve(&self, specifier: &str, parent: &str) -> Result<Resolved, EsmError> {
+        if specifier == "#" || specifier.starts_with("#/") || specifier.ends_with('/') {
+            return Err(EsmError::InvalidModuleSpecifier {
+                request: specifier.to_owned(),
+                reason: "is not a valid internal imports specifier name".to_owned(),
+                base: self.base.clone(),
+            });
+        }
+
+        let mut package_json_path = None;
+        if let Some((scope, package_json)) = self.lookup_package_scope(parent)? {
+            if let Some(imports) = package_json.imports() {
+                if let Target::Resolved(resolved) =
+                    self.imports_exports_resolve(scope, specifier, imports, true)?
+                {
+                    return Ok(resolved);
+                }
+            }
+            package_json_path = Some(self.package_json_path(scope)?);
+        }
+        Err(EsmError::PackageImportNotDefined {
+            specifier: specifier.to_owned(),
+            package_json: package_json_path,
+            base: self.base.clone(),
+        })
+    }
+
+    /// `PACKAGE_IMPORTS_EXPORTS_RESOLVE`: an exact key, or else the most specific `*` pattern matching `match_key`.
+    fn imports_exports_resolve(
+        &self,
+        package: &str,
+        match_key: &str,
+        map: &Map<String, Value>,
+        is_imports: bool,
+    ) -> Result<Target, EsmError> {
+        if !match_key.contains('*') {
+            if let Some(target) = map.get(match_key) {
+                return self.package_target_resolve(package, target, None, match_key, is_imports);
+            }
+        }
+
+        let mut best: Option<(&str, &str)> = None;
+        for key in map.keys() {
+            let star = match key.find('*') {
+                Some(star) => star,
+                None => continue,
+            };
+            let (key_base, trailer) = (&key[..star], &key[star + 1..]);
+            if match_key.starts_with(key_base)
+                && match_key.len() >= key.len()
+                && match_key.ends_with(trailer)
+                && !trailer.contains('*')
+                && pattern_key_compare(best.map_or("", |(best, _)| best), key) == Ordering::Greater
+            {
+                best = Some((key, &match_key[star..match_key.len() - trailer.len()]));
+            }
+        }
+        match best {
+            Some((key, pattern_match)) => self.package_target_resolve(
+                package,
+                &map[key],
+                Some(pattern_match),
+                key,
+                is_imports,
+            ),
+            None => Ok(Target::Unmatched),
+        }
+    }
+
+    /// `PACKAGE_TARGET_RESOLVE`
+    fn package_target_resolve(
+        &self,
+        package: &str,
+        target: &Value,
+        pattern_match: Option<&str>,
+        key: &str,
+        is_imports: bool,
+    ) -> Result<Target, EsmError> {
+        match target {
+            Value::String(target_str) => self
+                .package_target_string(package, target_str, target, pattern_match, key, is_imports)
+                .map(Target::Resolved),
+            Value::Array(targets) => {
+                // An empty array excludes the path. Otherwise the last excluded or invalid target is reported if
+                // none resolves, and an array of unmatched targets is unmatched too.
+                if targets.is_empty() {
+                    return Ok(Target::Excluded);
+                }
+                let mut last = Ok(Target::Unmatched);
+                for target in targets {
+                    match self.package_target_resolve(
+                        package,
+                        target,
+                        pattern_match,
+                        key,
+                        is_imports,
+                    ) {
+                        Ok(Target::Resolved(resolved)) => return Ok(Target::Resolved(resolved)),
+                        Ok(Target::Unmatched) => {}
+                        Ok(Target::Excluded) => last = Ok(Target::Excluded),
+                        Err(error @ EsmError::InvalidPackageTarget { .. }) => last = Err(error),
+                        Err(error) => return Err(error),
+                    }
+                }
+                last
+            }
+            Value::Object(conditions) => {
+                if conditions.keys().any(|condition| is_array_index(condition)) {
+                    return Err(self.invalid_package_config(
+                        package,
+                        "\"exports\" cannot contain numeric property keys.",
+                    ));
+                }
+                for (condition, target) in conditions {
+                    if condition == "default" || self.resolver.conditions.contains(condition) {
+                        match self.package_target_resolve(
+                            package,
+                            target,
+                            pattern_match,
+                            key,
+                            is_imports,
+                        )? {
+                            Target::Unmatched => {}
+                            resolved => return Ok(resolved),
+                        }
+                    }
+                }
+                Ok(Target::Unmatched)
+            }
+            Value::Null => Ok(Target::Excluded),
+            _ => Err(self.invalid_package_target(package, key, target, is_imports)),
+        }
+    }
+
+    fn package_target_string(
+        &self,
+        package: &str,
+        target_str: &str,
+        target: &Value,
+        pattern_match: Option<&str>,
+        key: &str,
+        is_imports: bool,
+    ) -> Result<Resolved, EsmError> {
+        let invalid_target = || self.invalid_package_target(package, key, target, is_imports);
+
+        if !target_str.starts_with("./") {
+            // `imports` may map to other packages.
+            if is_imports
+                && !target_str.starts_with("../")
+                && !target_str.starts_with('/')
+                && url_scheme(target_str).is_none()
+            {
+                let specifier = match pattern_match {
+                    Some(pattern_match) => target_str.replace('*', pattern_match),
+                    None => target_str.to_owned(),
+                };
+                return self.package_resolve(&specifier, &format!("{}package.json", package));
+            }
+            return Err(invalid_target());
+        }
+
+        if has_invalid_segment(&target_str[2..]) {
+            return Err(invalid_target());
+        }
+        let resolved = resolve_pathname(package, target_str);
+        if !resolved.starts_with(package) {
+            return Err(invalid_target());
+        }
+
+        let pattern_match = match pattern_match {
+            Some(pattern_match) => pattern_match,
+            None => return Ok(Resolved::Pathname(resolved)),
+        };
+        if has_invalid_segment(pattern_match) {
+            let field = if is_imports { "imports" } else { "exports" };
+            return Err(EsmError::InvalidModuleSpecifier {
+                request: key.replace('*', pattern_match),
+                reason: format!(
+                    "request is not a valid match in pattern \"{}\" for the \"{}\" resolution of {}",
+                    key,
+                    field,
+                    self.package_json_path(package)?
+                ),
+                base: self.base.clone(),
+            });
+        }
+        Ok(Resolved::Pathname(resolve_pathname(
+            "/",
+            &resolved.replace('*', pattern_match),
+        )))
+    }
+
+    /// `LOOKUP_PACKAGE_SCOPE`: the closest directory above `pathname` with a `package.json`, without leaving the
+    /// current `node_modules` package.
+    fn lookup_package_scope<'p>(
+        &self,
+        pathname: &'p str,
+    ) -> Result<Option<(&'p str, PackageJson)>, EsmError> {
+        let mut dir = dir_of(pathname);
+        loop {
+            if dir.ends_with("/node_modules/") {
+                return Ok(None);
+            }
+            if let Some(package_json) = self.read_package_json(dir)? {
+                return Ok(Some((dir, package_json)));
+            }
+            if dir == "/" {
+                return Ok(None);
+            }
+            dir = dir_of(&dir[..dir.len() - 1]);
+        }
+    }
+
+    /// The `package.json` in the directory pathname `package`, or `None` if there is none.
+    fn read_package_json(&self, package: &str) -> Result<Option<PackageJson>, EsmError> {
+        let path = self.package_json_path(package)?;
+        if !self.resolver.fs.is_file(&path) {
+            return Ok(None);
+        }
+        let invalid = |reason: String| EsmError::InvalidPackageConfig {
+            path: path.clone(),
+            reason,
+            base: self.base.clone(),
+        };
+        let source = self
+            .resolver
+            .fs
+            .read_to_string(&path)
+            .map_err(|error| invalid(error.to_string()))?;
+        PackageJson::parse(&source)
+            .map(Some)
+            .map_err(|error| invalid(error.to_string()))
+    }
+
+    fn package_json_path(&self, package: &str) -> Result<String, EsmError> {
+        self.path_of(&format!("{}package.json", package))
+    }
+
+    fn path_of(&self, pathname: &str) -> Result<String, EsmError> {
+        decode_pathname(pathname).ok_or_else(|| EsmError::InvalidUrl {
+            url: format!("file://{}", pathname),
+        })
+    }
+
+    fn invalid_package_config(&self, package: &str, reason: &str) -> EsmError {
+        EsmError::InvalidPackageConfig {
+            path: self
+                .package_json_path(package)
+                .unwrap_or_else(|_| package.to_owned()),
+            reason: reason.to_owned(),
+            base: self.base.clone(),
+        }
+    }
+
+    fn invalid_package_target(
+        &self,
+        package: &str,
+        key: &str,
+        target: &Value,
+        is_imports: bool,
+    ) -> EsmError {
+        EsmError::InvalidPackageTarget {
+            package_json: self
+                .package_json_path(package)
+                .unwrap_or_else(|_| package.to_owned()),
+            key: key.to_owned(),
+            // Node formats numbers and booleans as strings.
+            target: match target {
+                Value::Number(_) | Value::Bool(_) => Value::String(target.to_string()).to_string(),
+                _ => target.to_string(),
+            },
+            is_imports,
+            base: self.base.clone(),
+        }
+    }
+}
+
+fn is_relative_or_absolute(specifier: &str) -> bool {
+    posix::is_absolute(specifier)
+        || matches!(specifier, "." | "..")
+        || specifier.starts_with("./")
+        || specifier.starts_with("../")
+}
+
+/// The directory of a pathname, with its trailing `/`, as URL resolution sees it.
+fn dir_of(pathname: &str) -> &str {
+    &pathname[..pathname.rfind('/').map_or(0, |i| i + 1)]
+}
+
+/// Whether a target or pattern match has a `.`, `..` or `node_modules` segment, also when percent-encoded.
+fn has_invalid_segment(path: &str) -> bool {
+    path.split(['/', '\\']).any(|segment| {
+        let segment = decode_pathname(segment).unwrap_or_else(|| segment.to_owned());
+        matches!(
+            segment.to_ascii_lowercase().as_str(),
+            "." | ".." | "node_modules"
+        )
+    })
+}
+
+/// JavaScript's array index keys, which `exports` conditions can't use since objects would reorder them.
+fn is_array_index(key: &str) -> bool {
+    matches!(key.parse::<u32>(), Ok(index) if index != u32::MAX && index.to_string() == key)
+}
+
+/// `PATTERN_KEY_COMPARE`: `Less` if `a` is the more specific pattern, so it wins over `b`.
+fn pattern_key_compare(a: &str, b: &str) -> Ordering {
+    let (a_star, b_star) = (a.find('*'), b.find('*'));
+    let base_len = |key: &str, star: Option<usize>| star.map_or(key.len(), |star| star + 1);
+    match base_len(b, b_star).cmp(&base_len(a, a_star)) {
+        Ordering::Equal => {}
+        ordering => return ordering,
+    }
+    match (a_star, b_star) {
+        (None, _) => Ordering::Greater,
+        (_, None) => Ordering::Less,
+        _ => b.len().cmp(&a.len()),
+    }
+}
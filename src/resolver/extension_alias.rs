@@ -0,0 +1,125 @@
+use crate::posix;
+
+use super::fs::FileSystem;
+
+/// Swaps the extension of an import for the source files that may produce it, like TypeScript does under
+/// `moduleResolution: node16` and `bundler`, where `./foo.js` is written for `./foo.ts`.
+///
+/// With declarations on, the `.d.ts`, `.d.mts` and `.d.cts` companions of `.ts`, `.mts` and `.cts` candidates are
+/// tried after the TypeScript sources and before anything else, which is TypeScript's own order.
+/// ```rust
+/// use nodejs_path::resolver::ExtensionAlias;
+///
+/// let alias = ExtensionAlias::new().alias(".js", [".ts", ".tsx", ".js"]);
+/// assert_eq!(alias.candidates("./src/foo.js"), ["./src/foo.ts", "./src/foo.tsx", "./src/foo.js"]);
+/// assert_eq!(alias.candidates("./src/foo.css"), ["./src/foo.css"]);
+///
+/// let alias = ExtensionAlias::typescript().declarations(true);
+/// assert_eq!(
+///     alias.candidates("/app/lib/index.mjs"),
+///     ["/app/lib/index.mts", "/app/lib/index.d.mts", "/app/lib/index.mjs"]
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ExtensionAlias {
+    aliases: Vec<(String, Vec<String>)>,
+    declarations: bool,
+}
+
+impl ExtensionAlias {
+    /// No aliases, so every path is its only candidate.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The mapping of TypeScript's node16 resolution: `.js` to `.ts`, `.tsx`, `.js` and `.jsx`, `.jsx` to `.tsx` and
+    /// `.jsx`, `.mjs` to `.mts` and `.mjs`, and `.cjs` to `.cts` and `.cjs`.
+    pub fn typescript() -> Self {
+        Self::new()
+            .alias(".js", [".ts", ".tsx", ".js", ".jsx"])
+            .alias(".jsx", [".tsx", ".jsx"])
+            .alias(".mjs", [".mts", ".mjs"])
+            .alias(".cjs", [".cts", ".cjs"])
+    }
+
+    /// Maps paths ending in `ext` to the `targets` extensions, in order. Replaces an earlier alias of `ext`. Include
+    /// `ext` itself in `targets` to still try the path as written.
+    pub fn alias<I, S>(mut self, ext: &str, targets: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        let targets = targets.into_iter().map(Into::into).collect();
+        match self.aliases.iter_mut().find(|(from, _)| from == ext) {
+            Some((_, existing)) => *existing = targets,
+            None => self.aliases.push((ext.to_owned(), targets)),
+        }
+        self
+    }
+
+    /// Whether to add the declaration file of each TypeScript candidate.
+    pub fn declarations(mut self, declarations: bool) -> Self {
+        self.declarations = declarations;
+        self
+    }
+
+    /// The paths to try for `path`, in order. The extension is the one [`posix::parse`] finds, so dotfiles and
+    /// paths ending in `/` have none and are returned unchanged.
+    pub fn candidates(&self, path: &str) -> Vec<String> {
+        let ext = if path.ends_with('/') {
+            String::new()
+        } else {
+            posix::parse(path).ext
+        };
+        let targets = match self
+            .aliases
+            .iter()
+            .find(|(from, _)| !ext.is_empty() && *from == ext)
+        {
+            Some((_, targets)) => targets,
+            None => return vec![path.to_owned()],
+        };
+        let stem = &path[..path.len() - ext.len()];
+
+        let mut exts = targets.iter().map(String::as_str).collect::<Vec<_>>();
+        if self.declarations {
+            let companions = targets
+                .iter()
+                .filter_map(|target| declaration_ext(target))
+                .collect::<Vec<_>>();
+            let at = exts
+                .iter()
+                .position(|ext| !is_typescript_source(ext))
+                .unwrap_or(exts.len());
+            exts.splice(at..at, companions);
+        }
+        let mut candidates = Vec::with_capacity(exts.len());
+        for ext in exts {
+            let candidate = format!("{}{}", stem, ext);
+            if !candidates.contains(&candidate) {
+                candidates.push(candidate);
+            }
+        }
+        candidates
+    }
+
+    /// The first candidate of `path` that is a file.
+    pub fn find<F: FileSystem>(&self, fs: &F, path: &str) -> Option<String> {
+        self.candidates(path)
+            .into_iter()
+            .find(|candidate| fs.is_file(candidate))
+    }
+}
+
+fn is_typescript_source(ext: &str) -> bool {
+    matches!(ext, ".ts" | ".tsx" | ".mts" | ".cts")
+}
+
+fn declaration_ext(ext: &str) -> Option<&'static str> {
+    match ext {
+        ".ts" | ".tsx" => Some(".d.ts"),
+        ".mts" => Some(".d.mts"),
+        ".cts" => Some(".d.cts"),
+        _ => None,
+    }
+}
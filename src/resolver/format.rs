@@ -0,0 +1,124 @@
+use std::{fmt, io};
+
+use crate::posix;
+
+use super::{fs::FileSystem, package_scope::PackageScopes};
+
+/// How Node loads a file, named like the `format` of a loader hook.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ModuleFormat {
+    Module,
+    CommonJs,
+    Json,
+    Wasm,
+    /// A native `.node` addon.
+    Addon,
+}
+
+impl fmt::Display for ModuleFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            ModuleFormat::Module => "module",
+            ModuleFormat::CommonJs => "commonjs",
+            ModuleFormat::Json => "json",
+            ModuleFormat::Wasm => "wasm",
+            ModuleFormat::Addon => "addon",
+        })
+    }
+}
+
+/// The format of `.js` and extensionless files whose package scope has no `type`, like
+/// `--experimental-default-type`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum DefaultType {
+    #[default]
+    CommonJs,
+    Module,
+}
+
+/// Tells ES modules from CommonJS the way Node does, from the extension and the `type` of the nearest
+/// `package.json`.
+///
+/// `.mjs` and `.cjs` decide on their own, and `.json`, `.wasm` and `.node` have formats of their own. `.js` and
+/// extensionless files follow `"type": "module"` or `"type": "commonjs"`, and otherwise the default type. As with
+/// Node, a module default type leaves files under `node_modules` alone, so dependencies stay CommonJS.
+/// ```rust
+/// use nodejs_path::resolver::{DefaultType, FormatDetector, MemoryFileSystem, ModuleFormat};
+///
+/// let mut fs = MemoryFileSystem::new();
+/// fs.add_file("/app/package.json", r#"{ "type": "module" }"#)
+///     .add_file("/app/index.js", "")
+///     .add_file("/app/legacy.cjs", "")
+///     .add_file("/scripts/build.js", "");
+///
+/// let formats = FormatDetector::new(&fs);
+/// assert_eq!(formats.detect_module_format("/app/index.js").unwrap(), Some(ModuleFormat::Module));
+/// assert_eq!(formats.detect_module_format("/app/legacy.cjs").unwrap(), Some(ModuleFormat::CommonJs));
+/// assert_eq!(formats.detect_module_format("/scripts/build.js").unwrap(), Some(ModuleFormat::CommonJs));
+/// assert_eq!(formats.detect_module_format("/app/styles.css").unwrap(), None);
+///
+/// let formats = FormatDetector::new(&fs).default_type(DefaultType::Module);
+/// assert_eq!(formats.detect_module_format("/scripts/build.js").unwrap(), Some(ModuleFormat::Module));
+/// ```
+#[derive(Debug)]
+pub struct FormatDetector<F> {
+    scopes: PackageScopes<F>,
+    default_type: DefaultType,
+}
+
+impl<F: FileSystem> FormatDetector<F> {
+    pub fn new(fs: F) -> Self {
+        Self::with_scopes(PackageScopes::new(fs))
+    }
+
+    /// Reuses the package scopes, and their cache, of another lookup.
+    pub fn with_scopes(scopes: PackageScopes<F>) -> Self {
+        Self {
+            scopes,
+            default_type: DefaultType::default(),
+        }
+    }
+
+    /// Same as `--experimental-default-type`.
+    pub fn default_type(mut self, default_type: DefaultType) -> Self {
+        self.default_type = default_type;
+        self
+    }
+
+    pub fn scopes(&self) -> &PackageScopes<F> {
+        &self.scopes
+    }
+
+    /// The format of the file at `path`, or `None` for an extension Node has no loader for, like `.ts` or `.css`.
+    /// Errors come from reading the `package.json` of the scope.
+    pub fn detect_module_format(&self, path: &str) -> io::Result<Option<ModuleFormat>> {
+        let format = match posix::extname(path).as_str() {
+            ".mjs" => ModuleFormat::Module,
+            ".cjs" => ModuleFormat::CommonJs,
+            ".json" => ModuleFormat::Json,
+            ".wasm" => ModuleFormat::Wasm,
+            ".node" => ModuleFormat::Addon,
+            ".js" | "" => self.package_type(path)?,
+            _ => return Ok(None),
+        };
+        Ok(Some(format))
+    }
+
+    fn package_type(&self, path: &str) -> io::Result<ModuleFormat> {
+        let scope = self.scopes.find_package_scope(path)?;
+        match scope.as_ref().and_then(|scope| scope.package_type()) {
+            Some("module") => return Ok(ModuleFormat::Module),
+            Some("commonjs") => return Ok(ModuleFormat::CommonJs),
+            _ => {}
+        }
+        let in_node_modules = || {
+            posix::resolve_impl(&[path])
+                .split('/')
+                .any(|segment| segment == "node_modules")
+        };
+        Ok(match self.default_type {
+            DefaultType::Module if !in_node_modules() => ModuleFormat::Module,
+            _ => ModuleFormat::CommonJs,
+        })
+    }
+}
@@ -0,0 +1,200 @@
+use std::{collections::BTreeMap, io};
+
+use crate::posix;
+
+/// What a path points to, after following symlinks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum FileKind {
+    File,
+    Dir,
+}
+
+/// The filesystem queries the resolvers need. Paths are absolute posix paths.
+///
+/// [`OsFileSystem`] asks the operating system, and [`MemoryFileSystem`] serves a fixed tree, which is handy for tests
+/// and for resolving against a virtual project.
+pub trait FileSystem {
+    /// What is at `path` after following symlinks, or `None` if there is nothing or it can't be read.
+    fn kind(&self, path: &str) -> Option<FileKind>;
+
+    fn read_to_string(&self, path: &str) -> io::Result<String>;
+
+    /// `path` with every symlink resolved, like `realpath`.
+    fn canonicalize(&self, path: &str) -> io::Result<String>;
+
+    fn is_file(&self, path: &str) -> bool {
+        self.kind(path) == Some(FileKind::File)
+    }
+
+    fn is_dir(&self, path: &str) -> bool {
+        self.kind(path) == Some(FileKind::Dir)
+    }
+}
+
+impl<T: FileSystem + ?Sized> FileSystem for &T {
+    fn kind(&self, path: &str) -> Option<FileKind> {
+        (**self).kind(path)
+    }
+
+    fn read_to_string(&self, path: &str) -> io::Result<String> {
+        (**self).read_to_string(path)
+    }
+
+    fn canonicalize(&self, path: &str) -> io::Result<String> {
+        (**self).canonicalize(path)
+    }
+}
+
+/// The real filesystem, through `std::fs`.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OsFileSystem;
+
+impl FileSystem for OsFileSystem {
+    fn kind(&self, path: &str) -> Option<FileKind> {
+        let metadata = std::fs::metadata(path).ok()?;
+        Some(if metadata.is_dir() {
+            FileKind::Dir
+        } else {
+            FileKind::File
+        })
+    }
+
+    fn read_to_string(&self, path: &str) -> io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+
+    fn canonicalize(&self, path: &str) -> io::Result<String> {
+        posix::canonicalize(path)
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Entry {
+    File(String),
+    Dir,
+    Symlink(String),
+}
+
+/// An in-memory tree of files, directories and symlinks.
+///
+/// Parent directories are created as needed, and relative symlink targets are relative to the link's directory,
+/// as on disk.
+/// ```rust
+/// use nodejs_path::resolver::{FileKind, FileSystem, MemoryFileSystem};
+///
+/// let mut fs = MemoryFileSystem::new();
+/// fs.add_file("/app/node_modules/.pnpm/a@1/node_modules/a/index.js", "")
+///     .add_symlink("/app/node_modules/a", ".pnpm/a@1/node_modules/a");
+/// assert_eq!(fs.kind("/app/node_modules"), Some(FileKind::Dir));
+/// assert!(fs.is_file("/app/node_modules/a/index.js"));
+/// assert_eq!(
+///     fs.canonicalize("/app/node_modules/a/index.js").unwrap(),
+///     "/app/node_modules/.pnpm/a@1/node_modules/a/index.js"
+/// );
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct MemoryFileSystem {
+    entries: BTreeMap<String, Entry>,
+}
+
+// Same limit as Linux, so a symlink cycle fails instead of looping.
+const MAX_SYMLINK_HOPS: usize = 40;
+
+impl MemoryFileSystem {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn add_file(&mut self, path: &str, contents: &str) -> &mut Self {
+        self.insert(path, Entry::File(contents.to_owned()))
+    }
+
+    pub fn add_dir(&mut self, path: &str) -> &mut Self {
+        self.insert(path, Entry::Dir)
+    }
+
+    pub fn add_symlink(&mut self, path: &str, target: &str) -> &mut Self {
+        self.insert(path, Entry::Symlink(target.to_owned()))
+    }
+
+    fn insert(&mut self, path: &str, entry: Entry) -> &mut Self {
+        let path = posix::resolve_impl_with_cwd(&[path], "/");
+        let mut dir = posix::dirname(&path);
+        while dir != "/" && !self.entries.contains_key(&dir) {
+            let parent = posix::dirname(&dir);
+            self.entries.insert(dir, Entry::Dir);
+            dir = parent;
+        }
+        self.entries.insert(path, entry);
+        self
+    }
+
+    /// Resolves every symlink in `path`, or `None` if some component doesn't exist.
+    fn real_path(&self, path: &str) -> Option<String> {
+        let path = posix::resolve_impl_with_cwd(&[path], "/");
+        let mut pending = path.split('/').rev().map(str::to_owned).collect::<Vec<_>>();
+        let mut resolved = "/".to_owned();
+        let mut hops = 0;
+        while let Some(segment) = pending.pop() {
+            match segment.as_str() {
+                "" | "." => continue,
+                ".." => {
+                    resolved = posix::dirname(&resolved);
+                    continue;
+                }
+                _ => {}
+            }
+            let candidate = posix::join_impl(&[resolved.as_str(), &segment]);
+            match self.entries.get(&candidate)? {
+                Entry::Symlink(target) => {
+                    hops += 1;
+                    if hops > MAX_SYMLINK_HOPS {
+                        return None;
+                    }
+                    if posix::is_absolute(target) {
+                        resolved = "/".to_owned();
+                    }
+                    pending.extend(target.split('/').rev().map(str::to_owned));
+                }
+                _ => resolved = candidate,
+            }
+        }
+        Some(resolved)
+    }
+
+    fn entry(&self, path: &str) -> Option<&Entry> {
+        match self.real_path(path)? {
+            root if root == "/" => Some(&Entry::Dir),
+            real => self.entries.get(&real),
+        }
+    }
+}
+
+impl FileSystem for MemoryFileSystem {
+    fn kind(&self, path: &str) -> Option<FileKind> {
+        match self.entry(path)? {
+            Entry::File(_) => Some(FileKind::File),
+            Entry::Dir => Some(FileKind::Dir),
+            Entry::Symlink(_) => None,
+        }
+    }
+
+    fn read_to_string(&self, path: &str) -> io::Result<String> {
+        match self.entry(path) {
+            Some(Entry::File(contents)) => Ok(contents.clone()),
+            Some(_) => Err(io::Error::other(format!("{} is a directory", path))),
+            None => Err(not_found(path)),
+        }
+    }
+
+    fn canonicalize(&self, path: &str) -> io::Result<String> {
+        self.real_path(path).ok_or_else(|| not_found(path))
+    }
+}
+
+fn not_found(path: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::NotFound,
+        format!("no such file or directory: {}", path),
+    )
+}
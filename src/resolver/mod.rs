@@ -0,0 +1,28 @@
+// Align to https://nodejs.org/docs/latest-v20.x/api/esm.html#resolution-algorithm-specification
+
+pub(crate) mod browser;
+pub(crate) mod builtins;
+pub(crate) mod declarations;
+pub(crate) mod entry;
+pub(crate) mod esm;
+pub(crate) mod extension_alias;
+pub(crate) mod format;
+pub(crate) mod fs;
+pub(crate) mod package_json;
+pub(crate) mod package_scope;
+pub(crate) mod specifier;
+pub(crate) mod url;
+
+pub use browser::{BrowserField, BrowserTarget};
+pub use builtins::{is_builtin, Builtin, BuiltinModules, NodeVersion};
+pub use declarations::{types_package_name, DeclarationResolver};
+pub use entry::{EntryResolver, PackageEntry};
+pub use esm::{EsmError, EsmResolver};
+pub use extension_alias::ExtensionAlias;
+pub use format::{DefaultType, FormatDetector, ModuleFormat};
+pub use fs::{FileKind, FileSystem, MemoryFileSystem, OsFileSystem};
+pub use package_scope::{PackageScope, PackageScopes};
+pub use specifier::{
+    parse_specifier, validate_package_name, PackageNameError, Specifier, SpecifierError,
+};
+pub use url::{file_url_to_path, path_to_file_url};
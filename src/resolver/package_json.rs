@@ -0,0 +1,57 @@
+use std::io;
+
+use serde_json::{Map, Value};
+
+use crate::posix;
+
+use super::fs::FileSystem;
+
+/// A parsed `package.json`. Fields are read lazily, since each resolver only looks at a few of them.
+#[derive(Debug, Clone)]
+pub(crate) struct PackageJson {
+    value: Value,
+}
+
+impl PackageJson {
+    pub(crate) fn parse(source: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(source).map(|value| Self { value })
+    }
+
+    /// The `package.json` in `dir`, or `None` if there is none. Invalid JSON is an [`io::ErrorKind::InvalidData`]
+    /// error naming the file.
+    pub(crate) fn read<F: FileSystem>(fs: &F, dir: &str) -> io::Result<Option<Self>> {
+        let path = posix::join_impl(&[dir, "package.json"]);
+        if !fs.is_file(&path) {
+            return Ok(None);
+        }
+        let source = fs.read_to_string(&path)?;
+        Self::parse(&source)
+            .map(Some)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, format!("{}: {}", path, err)))
+    }
+
+    /// A top-level field, treating `null` as missing like Node does.
+    pub(crate) fn field(&self, name: &str) -> Option<&Value> {
+        self.value.get(name).filter(|value| !value.is_null())
+    }
+
+    pub(crate) fn str_field(&self, name: &str) -> Option<&str> {
+        self.field(name).and_then(Value::as_str)
+    }
+
+    pub(crate) fn name(&self) -> Option<&str> {
+        self.str_field("name")
+    }
+
+    pub(crate) fn main(&self) -> Option<&str> {
+        self.str_field("main")
+    }
+
+    pub(crate) fn exports(&self) -> Option<&Value> {
+        self.field("exports")
+    }
+
+    pub(crate) fn imports(&self) -> Option<&Map<String, Value>> {
+        self.field("imports").and_then(Value::as_object)
+    }
+}
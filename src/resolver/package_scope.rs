@@ -0,0 +1,127 @@
+use std::{
+    collections::HashMap,
+    io,
+    sync::{Arc, RwLock},
+};
+
+use serde_json::Value;
+
+use crate::posix;
+
+use super::{fs::FileSystem, package_json::PackageJson};
+
+/// The nearest `package.json` above a file, which decides its `type`, its package name and what it can import
+/// through `exports` self-references.
+#[derive(Debug, Clone)]
+pub struct PackageScope {
+    dir: String,
+    package_json: PackageJson,
+}
+
+impl PackageScope {
+    /// The directory holding the `package.json`.
+    pub fn dir(&self) -> &str {
+        &self.dir
+    }
+
+    pub fn package_json_path(&self) -> String {
+        posix::join_impl(&[self.dir.as_str(), "package.json"])
+    }
+
+    pub fn name(&self) -> Option<&str> {
+        self.package_json.name()
+    }
+
+    /// The `type` field, like `"module"` or `"commonjs"`, as written.
+    pub fn package_type(&self) -> Option<&str> {
+        self.package_json.str_field("type")
+    }
+
+    /// Any top-level field, with `null` read as missing.
+    pub fn field(&self, name: &str) -> Option<&Value> {
+        self.package_json.field(name)
+    }
+}
+
+/// Finds package scopes over a [`FileSystem`], remembering the answer for every directory on the way up so sibling
+/// files and nested directories reuse it. The cache can be shared between threads.
+///
+/// Like Node, the lookup stops at a `node_modules` directory, so a file directly in `node_modules` has no scope.
+/// ```rust
+/// use nodejs_path::resolver::{MemoryFileSystem, PackageScopes};
+///
+/// let mut fs = MemoryFileSystem::new();
+/// fs.add_file("/app/package.json", r#"{ "name": "app", "type": "module" }"#)
+///     .add_file("/app/src/deep/a.js", "")
+///     .add_file("/app/node_modules/stray.js", "");
+///
+/// let scopes = PackageScopes::new(&fs);
+/// let scope = scopes.find_package_scope("/app/src/deep/a.js").unwrap().unwrap();
+/// assert_eq!(scope.dir(), "/app");
+/// assert_eq!(scope.package_type(), Some("module"));
+/// assert!(scopes.find_package_scope("/app/node_modules/stray.js").unwrap().is_none());
+/// ```
+#[derive(Debug)]
+pub struct PackageScopes<F> {
+    fs: F,
+    // Directory to the scope it belongs to. Every directory walked through is cached, not only those holding a
+    // `package.json`.
+    cache: RwLock<HashMap<String, Option<Arc<PackageScope>>>>,
+}
+
+impl<F: FileSystem> PackageScopes<F> {
+    pub fn new(fs: F) -> Self {
+        Self {
+            fs,
+            cache: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// The scope of the file at `path`, starting from its directory. Relative paths are resolved against the
+    /// current directory. A `package.json` that isn't valid JSON is an [`io::ErrorKind::InvalidData`] error, which
+    /// isn't cached.
+    pub fn find_package_scope(&self, path: &str) -> io::Result<Option<Arc<PackageScope>>> {
+        let path = posix::resolve_impl(&[path]);
+        let mut dir = posix::dirname(&path);
+        let mut walked = Vec::new();
+        let scope = loop {
+            if let Some(scope) = self.cached(&dir) {
+                break scope;
+            }
+            if posix::basename_impl(&dir) == "node_modules" {
+                walked.push(dir);
+                break None;
+            }
+            if let Some(package_json) = PackageJson::read(&self.fs, &dir)? {
+                let scope = Some(Arc::new(PackageScope {
+                    dir: dir.clone(),
+                    package_json,
+                }));
+                walked.push(dir);
+                break scope;
+            }
+            let parent = posix::dirname(&dir);
+            let is_root = parent == dir;
+            walked.push(dir);
+            if is_root {
+                break None;
+            }
+            dir = parent;
+        };
+
+        let mut cache = self.cache.write().unwrap();
+        for dir in walked {
+            cache.insert(dir, scope.clone());
+        }
+        Ok(scope)
+    }
+
+    /// Forgets every lookup, for when `package.json` files have changed.
+    pub fn clear(&self) {
+        self.cache.write().unwrap().clear();
+    }
+
+    fn cached(&self, dir: &str) -> Option<Option<Arc<PackageScope>>> {
+        self.cache.read().unwrap().get(dir).cloned()
+    }
+}
@@ -0,0 +1,234 @@
+use std::{error, fmt};
+
+use crate::{posix, win32};
+
+use super::builtins::builtin_name;
+
+/// What kind of module a specifier names, from [`parse_specifier`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Specifier<'a> {
+    /// `.`, `..`, or a path starting with `./` or `../`, or their win32 forms.
+    Relative(&'a str),
+    /// A path that [`posix::is_absolute`] or [`win32::is_absolute`] accepts, like `/a`, `C:\a` or `\\server\share`.
+    Absolute(&'a str),
+    /// A package in `node_modules`. `subpath` is the rest of the specifier after `name`, either empty or starting
+    /// with `/`.
+    Bare { name: &'a str, subpath: &'a str },
+    /// A Node builtin, without its `node:` prefix.
+    Builtin(&'a str),
+    /// A `#` specifier mapped by the `imports` of the importing package.
+    PackageImport(&'a str),
+    /// An absolute URL, like `file:///a.js` or `https://esm.sh/x`.
+    Url(&'a str),
+}
+
+/// Why [`parse_specifier`] rejected a specifier.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SpecifierError {
+    Empty,
+    /// A bare specifier whose package name breaks npm's rules.
+    InvalidPackageName {
+        name: String,
+        reason: PackageNameError,
+    },
+    /// `#` alone, a `#/` specifier or one ending in `/`, which `imports` can't map.
+    InvalidPackageImport(String),
+    /// A `node:` specifier naming no builtin module.
+    UnknownBuiltinModule(String),
+}
+
+impl fmt::Display for SpecifierError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SpecifierError::Empty => write!(f, "specifier cannot be empty"),
+            SpecifierError::InvalidPackageName { name, reason } => {
+                write!(f, "invalid package name \"{}\": {}", name, reason)
+            }
+            SpecifierError::InvalidPackageImport(specifier) => write!(
+                f,
+                "\"{}\" is not a valid internal imports specifier name",
+                specifier
+            ),
+            SpecifierError::UnknownBuiltinModule(specifier) => {
+                write!(f, "no such built-in module: {}", specifier)
+            }
+        }
+    }
+}
+
+impl error::Error for SpecifierError {}
+
+/// Why [`validate_package_name`] rejected a name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum PackageNameError {
+    Empty,
+    /// `@scope` without a `/name`.
+    MissingScopedName,
+    LeadingPeriod,
+    LeadingUnderscore,
+    LeadingOrTrailingSpaces,
+    /// `node_modules` or `favicon.ico`.
+    Blocklisted,
+    /// A character `encodeURIComponent` would escape.
+    NotUrlFriendly,
+}
+
+impl fmt::Display for PackageNameError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            PackageNameError::Empty => "name length must be greater than zero",
+            PackageNameError::MissingScopedName => "scoped name must have a name after the scope",
+            PackageNameError::LeadingPeriod => "name cannot start with a period",
+            PackageNameError::LeadingUnderscore => "name cannot start with an underscore",
+            PackageNameError::LeadingOrTrailingSpaces => {
+                "name cannot contain leading or trailing spaces"
+            }
+            PackageNameError::Blocklisted => "name is blocklisted",
+            PackageNameError::NotUrlFriendly => "name can only contain URL-friendly characters",
+        })
+    }
+}
+
+/// Classifies an import specifier before resolution.
+///
+/// Relative and absolute paths are checked first, so `C:\a` is a path rather than a URL with the scheme `c:`. Bare
+/// specifiers are split into the package name, which includes the scope, and the subpath.
+/// ```rust
+/// use nodejs_path::resolver::{parse_specifier, Specifier};
+///
+/// assert_eq!(
+///     parse_specifier("@scope/pkg/sub/path.js").unwrap(),
+///     Specifier::Bare { name: "@scope/pkg", subpath: "/sub/path.js" }
+/// );
+/// assert_eq!(parse_specifier("pkg").unwrap(), Specifier::Bare { name: "pkg", subpath: "" });
+/// assert_eq!(parse_specifier("node:fs").unwrap(), Specifier::Builtin("fs"));
+/// assert_eq!(parse_specifier("./rel").unwrap(), Specifier::Relative("./rel"));
+/// assert_eq!(parse_specifier("#internal").unwrap(), Specifier::PackageImport("#internal"));
+/// assert_eq!(parse_specifier("https://esm.sh/x").unwrap(), Specifier::Url("https://esm.sh/x"));
+/// assert!(parse_specifier("_private").is_err());
+/// ```
+pub fn parse_specifier(specifier: &str) -> Result<Specifier<'_>, SpecifierError> {
+    if specifier.is_empty() {
+        return Err(SpecifierError::Empty);
+    }
+    if is_relative(specifier) {
+        return Ok(Specifier::Relative(specifier));
+    }
+    if posix::is_absolute(specifier) || win32::is_absolute(specifier) {
+        return Ok(Specifier::Absolute(specifier));
+    }
+    if let Some(name) = builtin_name(specifier) {
+        return Ok(Specifier::Builtin(name));
+    }
+    if let Some(scheme) = url_scheme(specifier) {
+        // Builtins only match the lowercase prefix, and Node's loader rejects `NODE:fs` as an unknown builtin.
+        if scheme.eq_ignore_ascii_case("node") {
+            return Err(SpecifierError::UnknownBuiltinModule(specifier.to_owned()));
+        }
+        return Ok(Specifier::Url(specifier));
+    }
+    if specifier.starts_with('#') {
+        if specifier == "#" || specifier.starts_with("#/") || specifier.ends_with('/') {
+            return Err(SpecifierError::InvalidPackageImport(specifier.to_owned()));
+        }
+        return Ok(Specifier::PackageImport(specifier));
+    }
+
+    let (name, subpath) = specifier.split_at(package_name_len(specifier));
+    validate_package_name(name).map_err(|reason| SpecifierError::InvalidPackageName {
+        name: name.to_owned(),
+        reason,
+    })?;
+    Ok(Specifier::Bare { name, subpath })
+}
+
+/// Checks a package name, scoped or not, against npm's rules for existing packages. The stricter rules for new
+/// packages, like lowercase only, are left out since older packages break them and still install.
+/// ```rust
+/// use nodejs_path::resolver::{validate_package_name, PackageNameError};
+///
+/// assert!(validate_package_name("@babel/core").is_ok());
+/// assert!(validate_package_name("JSONStream").is_ok());
+/// assert_eq!(validate_package_name(".bin"), Err(PackageNameError::LeadingPeriod));
+/// assert_eq!(validate_package_name("@scope"), Err(PackageNameError::MissingScopedName));
+/// assert_eq!(validate_package_name("a b"), Err(PackageNameError::NotUrlFriendly));
+/// ```
+pub fn validate_package_name(name: &str) -> Result<(), PackageNameError> {
+    if name.is_empty() {
+        return Err(PackageNameError::Empty);
+    }
+    if name.starts_with('.') {
+        return Err(PackageNameError::LeadingPeriod);
+    }
+    if name.starts_with('_') {
+        return Err(PackageNameError::LeadingUnderscore);
+    }
+    if name.trim() != name {
+        return Err(PackageNameError::LeadingOrTrailingSpaces);
+    }
+    if matches!(
+        name.to_ascii_lowercase().as_str(),
+        "node_modules" | "favicon.ico"
+    ) {
+        return Err(PackageNameError::Blocklisted);
+    }
+
+    let is_url_friendly = |part: &str| {
+        !part.is_empty()
+            && part
+                .bytes()
+                .all(|byte| byte.is_ascii_alphanumeric() || b"-_.!~*'()".contains(&byte))
+    };
+    match name.strip_prefix('@') {
+        Some(scoped) => {
+            let (scope, name) = scoped
+                .split_once('/')
+                .ok_or(PackageNameError::MissingScopedName)?;
+            if name.starts_with('.') {
+                Err(PackageNameError::LeadingPeriod)
+            } else if is_url_friendly(scope) && is_url_friendly(name) {
+                Ok(())
+            } else if name.is_empty() {
+                Err(PackageNameError::MissingScopedName)
+            } else {
+                Err(PackageNameError::NotUrlFriendly)
+            }
+        }
+        None if is_url_friendly(name) => Ok(()),
+        None => Err(PackageNameError::NotUrlFriendly),
+    }
+}
+
+/// The length of the package name at the start of a bare specifier: up to the first `/`, or the second one for
+/// scoped packages.
+pub(crate) fn package_name_len(specifier: &str) -> usize {
+    let first = match specifier.find('/') {
+        Some(first) => first,
+        None => return specifier.len(),
+    };
+    if !specifier.starts_with('@') {
+        return first;
+    }
+    specifier[first + 1..]
+        .find('/')
+        .map_or(specifier.len(), |second| first + 1 + second)
+}
+
+/// The scheme of a specifier that parses as an absolute URL.
+pub(crate) fn url_scheme(specifier: &str) -> Option<&str> {
+    let (scheme, _) = specifier.split_once(':')?;
+    let mut chars = scheme.chars();
+    let is_scheme = chars.next()?.is_ascii_alphabetic()
+        && chars.all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '-' | '.'));
+    is_scheme.then_some(scheme)
+}
+
+fn is_relative(specifier: &str) -> bool {
+    let rest = specifier
+        .strip_prefix("..")
+        .or_else(|| specifier.strip_prefix('.'));
+    match rest {
+        Some(rest) => rest.is_empty() || rest.starts_with(['/', '\\']),
+        None => false,
+    }
+}
@@ -0,0 +1,117 @@
+use crate::posix;
+
+/// Converts a posix path to a `file:` URL, like Node's `url.pathToFileURL`. Relative paths are resolved against
+/// [`posix::cwd`], and a trailing separator is kept.
+/// ```rust
+/// use nodejs_path::resolver::path_to_file_url;
+///
+/// assert_eq!(path_to_file_url("/srv/app/a b#1.js"), "file:///srv/app/a%20b%231.js");
+/// assert_eq!(path_to_file_url("/srv/../tmp/"), "file:///tmp/");
+/// ```
+pub fn path_to_file_url(path: &str) -> String {
+    let mut resolved = posix::resolve_impl(&[path]);
+    if path.ends_with(posix::sep) && resolved != "/" {
+        resolved.push(posix::sep);
+    }
+    format!("file://{}", encode_pathname(&resolved))
+}
+
+/// Converts a `file:` URL to a posix path, like Node's `url.fileURLToPath`. Returns `None` for other schemes,
+/// for hosts other than `localhost`, and for paths with an encoded `/` or an invalid escape.
+/// ```rust
+/// use nodejs_path::resolver::file_url_to_path;
+///
+/// assert_eq!(file_url_to_path("file:///srv/app/a%20b.js?v=1").unwrap(), "/srv/app/a b.js");
+/// assert_eq!(file_url_to_path("file://localhost/etc/../tmp/").unwrap(), "/tmp/");
+/// assert_eq!(file_url_to_path("file://server/share"), None);
+/// assert_eq!(file_url_to_path("https://example.com/a.js"), None);
+/// ```
+pub fn file_url_to_path(url: &str) -> Option<String> {
+    parse_file_url(url).and_then(|(pathname, _)| decode_pathname(&pathname))
+}
+
+/// The normalized, still encoded pathname of a `file:` URL and its `?query#fragment` suffix.
+pub(crate) fn parse_file_url(url: &str) -> Option<(String, &str)> {
+    let scheme_end = url.find(':')?;
+    if !url[..scheme_end].eq_ignore_ascii_case("file") {
+        return None;
+    }
+    let (rest, suffix) = split_suffix(&url[scheme_end + 1..]);
+    let rest = rest.replace('\\', "/");
+    let path = match rest.strip_prefix("//") {
+        Some(authority) => {
+            let (host, path) = authority.split_at(authority.find('/').unwrap_or(authority.len()));
+            if !host.is_empty() && !host.eq_ignore_ascii_case("localhost") {
+                return None;
+            }
+            path
+        }
+        None => &rest,
+    };
+    Some((resolve_pathname("/", path), suffix))
+}
+
+/// Splits a URL reference before its `?query` or `#fragment`.
+pub(crate) fn split_suffix(reference: &str) -> (&str, &str) {
+    reference.split_at(reference.find(['?', '#']).unwrap_or(reference.len()))
+}
+
+/// Resolves a relative URL path against the directory pathname `base`, like `new URL(reference, base)` does for
+/// the path. `\` separates segments as in any special URL, `%2e` counts as a `.` in dot segments, and a reference
+/// ending in a `.`, `..` or empty segment resolves to a directory with a trailing `/`.
+pub(crate) fn resolve_pathname(base: &str, reference: &str) -> String {
+    let reference = reference
+        .replace('\\', "/")
+        .split('/')
+        .map(|segment| match segment.to_ascii_lowercase().as_str() {
+            "%2e" => ".",
+            ".%2e" | "%2e." | "%2e%2e" => "..",
+            _ => segment,
+        })
+        .collect::<Vec<_>>()
+        .join("/");
+    let mut resolved = posix::resolve_impl_with_cwd(&[base, &reference], "/");
+    let last_segment = reference.rsplit('/').next().unwrap_or("");
+    if matches!(last_segment, "" | "." | "..") && resolved != "/" {
+        resolved.push('/');
+    }
+    resolved
+}
+
+/// Percent-encodes a path with the URL path set, plus `%` and `\`, as `pathToFileURL` does.
+pub(crate) fn encode_pathname(path: &str) -> String {
+    let mut out = String::with_capacity(path.len());
+    for byte in path.bytes() {
+        if byte <= b' ' || byte >= 0x7f || b"\"#%<>?\\`{}".contains(&byte) {
+            out.push_str(&format!("%{:02X}", byte));
+        } else {
+            out.push(byte as char);
+        }
+    }
+    out
+}
+
+/// Decodes a pathname back to a path. Like `decodeURIComponent`, invalid escapes and UTF-8 are rejected, and so is
+/// an encoded `/`, which `fileURLToPath` refuses.
+pub(crate) fn decode_pathname(pathname: &str) -> Option<String> {
+    let bytes = pathname.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let byte = bytes
+                .get(i + 1..i + 3)
+                .filter(|hex| hex.iter().all(u8::is_ascii_hexdigit))
+                .and_then(|hex| u8::from_str_radix(core::str::from_utf8(hex).ok()?, 16).ok())?;
+            if byte == b'/' {
+                return None;
+            }
+            out.push(byte);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
@@ -0,0 +1,121 @@
+use crate as nodejs_path;
+
+use nodejs_path::resolver::{BrowserField, BrowserTarget};
+use serde_json::json;
+
+const PKG: &str = "/app/node_modules/pkg";
+
+fn path(path: &str) -> BrowserTarget {
+    BrowserTarget::Path(path.to_owned())
+}
+
+#[test]
+fn main() {
+    let browser = BrowserField::new(PKG, &json!("./dist/browser.js"));
+    assert_eq!(
+        browser.main(),
+        Some("/app/node_modules/pkg/dist/browser.js")
+    );
+    assert_eq!(browser.map_path("/app/node_modules/pkg/index.js"), None);
+    assert!(!browser.is_empty());
+
+    let browser = BrowserField::new(PKG, &json!("dist/browser"));
+    assert_eq!(browser.main(), Some("/app/node_modules/pkg/dist/browser"));
+
+    for ignored in [json!(null), json!(true), json!(1), json!(["a"])] {
+        assert!(BrowserField::new(PKG, &ignored).is_empty());
+    }
+}
+
+#[test]
+fn paths() {
+    let browser = BrowserField::new(
+        PKG,
+        &json!({
+            "./lib/a.js": "./lib/a-browser.js",
+            "lib/b": "./shims/b.js",
+            "./lib/c": false,
+            "./lib/../lib/d.js": "./lib/d-browser",
+            "/lib/e.js": "../shared/e.js",
+            "./lib/f.js": true,
+            "./lib/g.js": 1,
+        }),
+    );
+    let cases = [
+        (
+            "/app/node_modules/pkg/lib/a.js",
+            Some(path("/app/node_modules/pkg/lib/a-browser.js")),
+        ),
+        ("/app/node_modules/pkg/lib/a", None),
+        (
+            "/app/node_modules/pkg/lib/b.js",
+            Some(path("/app/node_modules/pkg/shims/b.js")),
+        ),
+        (
+            "/app/node_modules/pkg/lib/b",
+            Some(path("/app/node_modules/pkg/shims/b.js")),
+        ),
+        (
+            "/app/node_modules/pkg/lib/b.json",
+            Some(path("/app/node_modules/pkg/shims/b.js")),
+        ),
+        (
+            "/app/node_modules/pkg/lib/c.mjs",
+            Some(BrowserTarget::Empty),
+        ),
+        (
+            "/app/node_modules/pkg/lib//./c.js",
+            Some(BrowserTarget::Empty),
+        ),
+        (
+            "/app/node_modules/pkg/lib/d.js",
+            Some(path("/app/node_modules/pkg/lib/d-browser")),
+        ),
+        (
+            "/app/node_modules/pkg/lib/e.js",
+            Some(path("/app/node_modules/shared/e.js")),
+        ),
+        ("/app/node_modules/pkg/lib/f.js", None),
+        ("/app/node_modules/pkg/lib/g.js", None),
+        ("/app/node_modules/other/lib/a.js", None),
+    ];
+    for (resolved, expected) in cases {
+        assert_eq!(
+            browser.map_path(resolved),
+            expected.as_ref(),
+            "{}",
+            resolved
+        );
+    }
+}
+
+#[test]
+fn modules() {
+    let browser = BrowserField::new(
+        PKG,
+        &json!({
+            "fs": false,
+            "http": "stream-http",
+            "@scope/dep": "./shims/dep.js",
+            "buffer/": "buffer-browser",
+            "./local.js": false,
+        }),
+    );
+    assert_eq!(browser.map_module("fs"), Some(&BrowserTarget::Empty));
+    assert_eq!(
+        browser.map_module("http"),
+        Some(&BrowserTarget::Module("stream-http".to_owned()))
+    );
+    assert_eq!(
+        browser.map_module("@scope/dep"),
+        Some(&path("/app/node_modules/pkg/shims/dep.js"))
+    );
+    assert_eq!(
+        browser.map_module("buffer/"),
+        Some(&BrowserTarget::Module("buffer-browser".to_owned()))
+    );
+    assert_eq!(browser.map_module("buffer"), None);
+    assert_eq!(browser.map_module("fs/promises"), None);
+    assert_eq!(browser.map_module("./local.js"), None);
+    assert_eq!(browser.main(), None);
+}
@@ -0,0 +1,95 @@
+use crate as nodejs_path;
+
+use nodejs_path::resolver::{is_builtin, BuiltinModules, NodeVersion};
+
+#[test]
+fn versions() {
+    assert_eq!("v20.11.1".parse(), Ok(NodeVersion::new(20, 11, 1)));
+    assert_eq!("18.0".parse(), Ok(NodeVersion::new(18, 0, 0)));
+    assert_eq!("22".parse(), Ok(NodeVersion::new(22, 0, 0)));
+    assert_eq!("v".parse::<NodeVersion>(), Err(()));
+    assert_eq!("20.x".parse::<NodeVersion>(), Err(()));
+    assert_eq!(NodeVersion::new(20, 11, 1).to_string(), "v20.11.1");
+    assert!(NodeVersion::new(20, 9, 0) < NodeVersion::new(20, 11, 0));
+}
+
+#[test]
+fn catalog() {
+    let latest = BuiltinModules::latest();
+    let names = latest.iter().map(|b| b.name).collect::<Vec<_>>();
+    let mut sorted = names.clone();
+    sorted.sort_unstable();
+    assert_eq!(names, sorted);
+
+    // `module.builtinModules` of node v20.20.2, which has `node:sea` and `node:test/reporters` through backports.
+    let node20 = BuiltinModules::for_version(NodeVersion::new(20, 20, 2));
+    let expected = "_http_agent _http_client _http_common _http_incoming _http_outgoing _http_server \
+        _stream_duplex _stream_passthrough _stream_readable _stream_transform _stream_wrap _stream_writable \
+        _tls_common _tls_wrap assert assert/strict async_hooks buffer child_process cluster console constants \
+        crypto dgram diagnostics_channel dns dns/promises domain events fs fs/promises http http2 https inspector \
+        inspector/promises module net os path path/posix path/win32 perf_hooks process punycode querystring \
+        readline readline/promises repl stream stream/consumers stream/promises stream/web string_decoder sys \
+        timers timers/promises tls trace_events tty url util util/types v8 vm wasi worker_threads zlib";
+    let listed = node20
+        .iter()
+        .filter(|b| !b.prefix_only)
+        .map(|b| b.name)
+        .collect::<Vec<_>>();
+    assert_eq!(listed, expected.split_whitespace().collect::<Vec<_>>());
+    assert!(node20.is_builtin("node:sea"));
+    assert!(node20.is_builtin("node:test/reporters"));
+    assert!(!node20.is_builtin("node:sqlite"));
+}
+
+#[test]
+fn prefixes() {
+    for specifier in [
+        "fs",
+        "node:fs",
+        "fs/promises",
+        "node:test",
+        "node:test/reporters",
+        "node:sqlite",
+    ] {
+        assert!(is_builtin(specifier), "{:?}", specifier);
+    }
+    for specifier in [
+        "test",
+        "sqlite",
+        "sea",
+        "node:",
+        "node:lodash",
+        "lodash",
+        "fs/",
+        "node:fs/",
+        "NODE:fs",
+    ] {
+        assert!(!is_builtin(specifier), "{:?}", specifier);
+    }
+
+    let builtin = BuiltinModules::latest().get("node:test").unwrap();
+    assert_eq!(builtin.name, "test");
+    assert!(builtin.prefix_only);
+}
+
+#[test]
+fn backports() {
+    let has = |version: NodeVersion, specifier| {
+        BuiltinModules::for_version(version).is_builtin(specifier)
+    };
+    assert!(!has(NodeVersion::new(16, 16, 0), "node:test"));
+    assert!(has(NodeVersion::new(16, 17, 0), "node:test"));
+    assert!(!has(NodeVersion::new(17, 9, 0), "node:test"));
+    assert!(has(NodeVersion::new(18, 0, 0), "node:test"));
+
+    assert!(!has(NodeVersion::new(14, 16, 0), "diagnostics_channel"));
+    assert!(has(NodeVersion::new(14, 17, 0), "diagnostics_channel"));
+    assert!(!has(NodeVersion::new(15, 0, 0), "diagnostics_channel"));
+    assert!(has(NodeVersion::new(15, 1, 0), "diagnostics_channel"));
+
+    assert!(
+        !has(NodeVersion::new(14, 0, 0), "fs/promises") || has(NodeVersion::new(14, 0, 0), "fs")
+    );
+    assert!(!has(NodeVersion::new(12, 22, 0), "fs/promises"));
+    assert!(!has(NodeVersion::new(8, 0, 0), "worker_threads"));
+}
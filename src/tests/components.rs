@@ -0,0 +1,238 @@
+use crate as nodejs_path;
+
+use nodejs_path::{posix, win32, Component};
+
+fn basename(path: &str) -> &str {
+    match posix::components(path).next_back() {
+        Some(Component::Root(_)) | None => "",
+        Some(component) => component.as_str(),
+    }
+}
+
+fn dirname(path: &str) -> &str {
+    let mut components = posix::components(path);
+    match components.next_back() {
+        Some(Component::Root(root)) => root,
+        None => ".",
+        Some(_) => match components.as_str() {
+            "" => ".",
+            dir => dir,
+        },
+    }
+}
+
+/// Lexical components joined back together, which is what `normalize` does minus the trailing separator.
+fn lexical(path: &str) -> String {
+    let mut components = posix::components(path).lexical().peekable();
+    let root = match components.peek().copied() {
+        Some(Component::Root(root)) => {
+            components.next();
+            root
+        }
+        _ => "",
+    };
+    let joined = components.map(|c| c.as_str()).collect::<Vec<_>>().join("/");
+    match (root, joined.as_str()) {
+        ("", "") => ".".to_owned(),
+        (root, joined) => format!("{}{}", root, joined),
+    }
+}
+
+const PATHS: &[&str] = &[
+    "",
+    ".",
+    "..",
+    "/",
+    "////",
+    "/a",
+    "/a/b",
+    "/a/b/",
+    "/a//b/c/",
+    "a",
+    "a/",
+    "a/b/c",
+    "./a",
+    "../a/..",
+    "/foo/bar/baz/asdf/quux.html",
+    "/../a/./b/../../..",
+    "a/b/../../..",
+    "é/🦀/./..",
+];
+
+#[cfg(target_family = "unix")]
+#[test]
+fn test_posix() {
+    let components = posix::components("/foo//bar/./../baz/").collect::<Vec<_>>();
+    assert_eq!(
+        components,
+        [
+            Component::Root("/"),
+            Component::Normal("foo"),
+            Component::Normal("bar"),
+            Component::CurDir,
+            Component::ParentDir,
+            Component::Normal("baz"),
+        ]
+    );
+    let mut reversed = posix::components("/foo//bar/./../baz/")
+        .rev()
+        .collect::<Vec<_>>();
+    reversed.reverse();
+    assert_eq!(reversed, components);
+
+    assert_eq!(posix::components("").next(), None);
+    assert_eq!(
+        posix::components("///").collect::<Vec<_>>(),
+        [Component::Root("/")]
+    );
+    assert_eq!(
+        posix::components("///").next_back(),
+        Some(Component::Root("/"))
+    );
+
+    // Both ends meet in the middle.
+    let mut components = posix::components("/a/b/c");
+    assert_eq!(components.next(), Some(Component::Root("/")));
+    assert_eq!(components.next_back(), Some(Component::Normal("c")));
+    assert_eq!(components.next(), Some(Component::Normal("a")));
+    assert_eq!(components.next_back(), Some(Component::Normal("b")));
+    assert_eq!(components.next(), None);
+    assert_eq!(components.next_back(), None);
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn test_posix_lexical() {
+    let lexical_components = |path| posix::components(path).lexical().collect::<Vec<_>>();
+    assert_eq!(
+        lexical_components("a/b/../c"),
+        [Component::Normal("a"), Component::Normal("c")]
+    );
+    assert_eq!(
+        lexical_components("/../a"),
+        [Component::Root("/"), Component::Normal("a")]
+    );
+    assert_eq!(lexical_components("../a/.."), [Component::ParentDir]);
+    assert_eq!(lexical_components("./."), []);
+
+    let mut components = posix::components("a/b/../c/d/..").lexical();
+    assert_eq!(components.next_back(), Some(Component::Normal("c")));
+    assert_eq!(components.next(), Some(Component::Normal("a")));
+    assert_eq!(components.next(), None);
+
+    for path in PATHS {
+        let normalized = posix::normalize(path);
+        let expected = match normalized.trim_end_matches('/') {
+            "" => "/",
+            trimmed => trimmed,
+        };
+        assert_eq!(lexical(path), expected, "normalize({:?})", path);
+    }
+
+    // Deep paths stay linear: every segment but the last is cancelled by a `..` at the far end.
+    let deep = format!("{}{}z", "a/".repeat(50_000), "../".repeat(49_999));
+    let mut components = posix::components(&deep).lexical();
+    assert_eq!(components.next(), Some(Component::Normal("a")));
+    assert_eq!(components.next(), Some(Component::Normal("z")));
+    assert_eq!(components.next(), None);
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn test_posix_basename_dirname() {
+    for path in PATHS {
+        assert_eq!(
+            basename(path),
+            posix::basename_impl(path),
+            "basename({:?})",
+            path
+        );
+        assert_eq!(dirname(path), posix::dirname(path), "dirname({:?})", path);
+    }
+
+    // Node only drops the last of a run of separators before the basename, `as_str` drops all of them.
+    assert_eq!(posix::dirname("/a//b"), "/a/");
+    assert_eq!(dirname("/a//b"), "/a");
+}
+
+#[cfg(target_family = "unix")]
+#[test]
+fn test_win32() {
+    let components = |path| win32::components(path).collect::<Vec<_>>();
+    assert_eq!(
+        components("C:\\foo/bar\\"),
+        [
+            Component::Root("C:\\"),
+            Component::Normal("foo"),
+            Component::Normal("bar")
+        ]
+    );
+    assert_eq!(
+        components("C:foo"),
+        [Component::Root("C:"), Component::Normal("foo")]
+    );
+    assert_eq!(
+        components("\\foo"),
+        [Component::Root("\\"), Component::Normal("foo")]
+    );
+    assert_eq!(
+        components("\\\\server\\share\\foo"),
+        [
+            Component::Root("\\\\server\\share\\"),
+            Component::Normal("foo")
+        ]
+    );
+    assert_eq!(
+        components("//server/share"),
+        [Component::Root("//server/share")]
+    );
+    assert_eq!(
+        components("//server"),
+        [Component::Root("/"), Component::Normal("server")]
+    );
+    assert_eq!(
+        components("foo\\..\\bar"),
+        [
+            Component::Normal("foo"),
+            Component::ParentDir,
+            Component::Normal("bar"),
+        ]
+    );
+    assert_eq!(
+        win32::components("C:\\foo\\..\\..\\bar")
+            .lexical()
+            .collect::<Vec<_>>(),
+        [Component::Root("C:\\"), Component::Normal("bar")]
+    );
+    // A drive-relative root keeps the `..` that climb above it, like `win32.normalize`.
+    let lexical = |path| win32::components(path).lexical().collect::<Vec<_>>();
+    let cases: [(&str, &[Component]); 6] = [
+        (
+            "C:..\\a",
+            &[
+                Component::Root("C:"),
+                Component::ParentDir,
+                Component::Normal("a"),
+            ],
+        ),
+        ("C:..", &[Component::Root("C:"), Component::ParentDir]),
+        ("c:..", &[Component::Root("c:"), Component::ParentDir]),
+        ("C:../.", &[Component::Root("C:"), Component::ParentDir]),
+        ("C:..\\", &[Component::Root("C:"), Component::ParentDir]),
+        (
+            "C:a\\..\\..\\b",
+            &[
+                Component::Root("C:"),
+                Component::ParentDir,
+                Component::Normal("b"),
+            ],
+        ),
+    ];
+    for (path, expected) in cases {
+        assert_eq!(lexical(path), expected, "{:?}", path);
+    }
+    assert_eq!(
+        win32::components("\\\\server\\share\\a\\b").next_back(),
+        Some(Component::Normal("b"))
+    );
+}
@@ -0,0 +1,120 @@
+//! Node's own path test vectors, loaded from the data files in `conformance/`. Those are produced by
+//! `conformance/generate.js`, so re-running it with a new node release re-syncs the expectations.
+//!
+//! The `win32` sections are kept in the data files, but only checked once the matching win32 method is ported.
+
+use crate as nodejs_path;
+
+use nodejs_path::{posix, Parsed};
+use serde_json::Value;
+
+fn load(data: &str) -> Value {
+    serde_json::from_str(data).unwrap()
+}
+
+fn str_of(value: &Value) -> &str {
+    value.as_str().unwrap()
+}
+
+fn parsed_of(value: &Value) -> Parsed {
+    let field = |name: &str| value.get(name).map_or("", str_of).to_owned();
+    Parsed {
+        root: field("root"),
+        dir: field("dir"),
+        base: field("base"),
+        ext: field("ext"),
+        name: field("name"),
+    }
+}
+
+fn cases<'a>(data: &'a Value, flavor: &str, section: &str) -> &'a [Value] {
+    let cases = match section {
+        "" => &data[flavor],
+        section => &data[flavor][section],
+    };
+    cases.as_array().unwrap()
+}
+
+#[test]
+fn posix_parse_format() {
+    let data = load(include_str!("../../conformance/parse-format.json"));
+
+    // checkParseFormat
+    for case in cases(&data, "posix", "parseFormat") {
+        let input = str_of(&case["input"]);
+        let output = posix::parse(input);
+        assert_eq!(output, parsed_of(&case["parsed"]), "parse({:?})", input);
+        assert_eq!(
+            posix::format(output.clone()),
+            input,
+            "format(parse({:?}))",
+            input
+        );
+        assert_eq!(
+            output.root,
+            str_of(&case["root"]),
+            "parse({:?}).root",
+            input
+        );
+        assert!(output.dir.starts_with(&output.root));
+        if !output.dir.is_empty() {
+            assert_eq!(output.dir, posix::dirname(input), "dirname({:?})", input);
+        }
+        assert_eq!(
+            output.base,
+            posix::basename_impl(input),
+            "basename({:?})",
+            input
+        );
+        assert_eq!(output.ext, posix::extname(input), "extname({:?})", input);
+    }
+
+    // checkSpecialCaseParseFormat and trailingTests
+    for case in cases(&data, "posix", "parse") {
+        let input = str_of(&case["input"]);
+        assert_eq!(
+            posix::parse(input),
+            parsed_of(&case["expected"]),
+            "parse({:?})",
+            input
+        );
+    }
+
+    // checkFormat
+    for case in cases(&data, "posix", "format") {
+        assert_eq!(
+            posix::format(parsed_of(&case["input"])),
+            str_of(&case["expected"]),
+            "format({})",
+            case["input"]
+        );
+    }
+}
+
+#[test]
+fn posix_extname() {
+    let data = load(include_str!("../../conformance/extname.json"));
+    for case in cases(&data, "posix", "") {
+        let input = str_of(&case["args"][0]);
+        assert_eq!(
+            posix::extname(input),
+            str_of(&case["expected"]),
+            "extname({:?})",
+            input
+        );
+    }
+}
+
+#[test]
+fn posix_basename() {
+    let data = load(include_str!("../../conformance/basename.json"));
+    for case in cases(&data, "posix", "") {
+        let args = case["args"].as_array().unwrap();
+        let actual = match args.as_slice() {
+            [path] => posix::basename_impl(str_of(path)),
+            [path, ext] => posix::basename_impl_without_ext(str_of(path), str_of(ext)),
+            _ => unreachable!("{}", case),
+        };
+        assert_eq!(actual, str_of(&case["expected"]), "basename({:?})", args);
+    }
+}
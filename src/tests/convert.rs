@@ -0,0 +1,89 @@
+use crate as nodejs_path;
+
+use nodejs_path::{to_posix, to_win32, DriveMapping};
+
+#[test]
+fn win32_to_posix() {
+    let cases = [
+        ("C:\\", "/c", "/mnt/c"),
+        ("C:\\Users\\me", "/c/Users/me", "/mnt/c/Users/me"),
+        ("c:/Users/me/", "/c/Users/me/", "/mnt/c/Users/me/"),
+        ("D:\\a\\..\\..\\b", "/d/b", "/mnt/d/b"),
+        ("\\\\?\\C:\\foo", "/c/foo", "/mnt/c/foo"),
+        ("\\\\srv\\share", "//srv/share", "//srv/share"),
+        ("\\\\srv\\share\\a\\..\\b", "//srv/share/b", "//srv/share/b"),
+        (
+            "\\\\?\\UNC\\srv\\share\\a",
+            "//srv/share/a",
+            "//srv/share/a",
+        ),
+        ("\\foo\\bar", "/foo/bar", "/foo/bar"),
+        ("\\", "/", "/"),
+        ("foo\\..\\..\\bar", "../bar", "../bar"),
+        ("", ".", "."),
+    ];
+    for (path, msys, wsl) in cases {
+        assert_eq!(
+            to_posix(path, DriveMapping::Msys).unwrap(),
+            msys,
+            "{:?}",
+            path
+        );
+        assert_eq!(
+            to_posix(path, DriveMapping::Wsl).unwrap(),
+            wsl,
+            "{:?}",
+            path
+        );
+    }
+    assert_eq!(
+        to_posix("C:\\x", DriveMapping::Prefix("/cygdrive/")).unwrap(),
+        "/cygdrive/c/x"
+    );
+
+    for path in [
+        "C:foo",
+        "C:",
+        "\\\\.\\pipe\\name",
+        "\\\\?\\GLOBALROOT\\Device\\x",
+        "\\\\?\\UNC\\srv",
+    ] {
+        assert_eq!(to_posix(path, DriveMapping::Msys), None, "{:?}", path);
+    }
+}
+
+#[test]
+fn posix_to_win32() {
+    let cases = [
+        ("/c", DriveMapping::Msys, "C:\\"),
+        ("/c/Users/me/", DriveMapping::Msys, "C:\\Users\\me\\"),
+        ("/mnt/d/x/../y", DriveMapping::Wsl, "D:\\y"),
+        ("/mnt/d", DriveMapping::Msys, "\\mnt\\d"),
+        ("/cygdrive/c/x", DriveMapping::Prefix("/cygdrive/"), "C:\\x"),
+        ("//srv/share", DriveMapping::Msys, "\\\\srv\\share"),
+        (
+            "//srv/share/a/../../b",
+            DriveMapping::Msys,
+            "\\\\srv\\share\\b",
+        ),
+        ("//srv", DriveMapping::Msys, "\\srv"),
+        ("///srv/share", DriveMapping::Msys, "\\srv\\share"),
+        ("/usr/bin", DriveMapping::Msys, "\\usr\\bin"),
+        ("a/./b", DriveMapping::Msys, "a\\b"),
+        ("", DriveMapping::Msys, "."),
+    ];
+    for (path, drives, expected) in cases {
+        assert_eq!(to_win32(path, drives).unwrap(), expected, "{:?}", path);
+    }
+
+    assert_eq!(to_win32("/a\\b", DriveMapping::Msys), None);
+    assert_eq!(to_win32("c:x", DriveMapping::Msys), None);
+}
+
+#[test]
+fn rooted_paths_that_look_like_drives() {
+    assert_eq!(to_posix("\\a\\", DriveMapping::Msys), None);
+    assert_eq!(to_posix("\\a\\", DriveMapping::Wsl).unwrap(), "/a/");
+    assert_eq!(to_posix("\\mnt\\c", DriveMapping::Wsl), None);
+    assert_eq!(to_posix("\\mnt\\c", DriveMapping::Msys).unwrap(), "/mnt/c");
+}
@@ -0,0 +1,334 @@
+use crate as nodejs_path;
+
+use std::io;
+
+use nodejs_path::resolver::{types_package_name, DeclarationResolver, MemoryFileSystem};
+
+fn project() -> MemoryFileSystem {
+    let mut fs = MemoryFileSystem::new();
+    fs.add_file(
+        "/app/node_modules/typings/package.json",
+        r#"{ "typings": "lib/main.d.ts", "types": "nope.d.ts" }"#,
+    )
+    .add_file("/app/node_modules/typings/lib/main.d.ts", "")
+    .add_file(
+        "/app/node_modules/types-dir/package.json",
+        r#"{ "types": "./types" }"#,
+    )
+    .add_file("/app/node_modules/types-dir/types/index.d.ts", "")
+    .add_file(
+        "/app/node_modules/from-main/package.json",
+        r#"{ "main": "./dist/main.js" }"#,
+    )
+    .add_file("/app/node_modules/from-main/dist/main.js", "")
+    .add_file("/app/node_modules/from-main/dist/main.d.ts", "")
+    .add_file(
+        "/app/node_modules/esm-main/package.json",
+        r#"{ "main": "./dist/main.mjs" }"#,
+    )
+    .add_file("/app/node_modules/esm-main/dist/main.d.mts", "")
+    .add_file("/app/node_modules/index-only/index.d.ts", "")
+    .add_file("/app/node_modules/index-only/sub.d.ts", "")
+    .add_file("/app/node_modules/index-only/nested/index.d.ts", "")
+    .add_file(
+        "/app/node_modules/index-only/typed-dir/package.json",
+        r#"{ "types": "main.d.ts" }"#,
+    )
+    .add_file("/app/node_modules/index-only/typed-dir/main.d.ts", "")
+    .add_file("/app/node_modules/index-only/file.cjs", "")
+    .add_file("/app/node_modules/index-only/file.d.cts", "")
+    .add_file(
+        "/app/node_modules/untyped/package.json",
+        r#"{ "main": "index.js" }"#,
+    )
+    .add_file("/app/node_modules/untyped/index.js", "")
+    .add_file("/app/node_modules/@types/untyped/index.d.ts", "")
+    .add_file("/app/node_modules/@scope/pkg/index.js", "")
+    .add_file(
+        "/node_modules/@types/scope__pkg/package.json",
+        r#"{ "types": "types.d.ts" }"#,
+    )
+    .add_file("/node_modules/@types/scope__pkg/types.d.ts", "")
+    .add_file("/node_modules/@types/scope__pkg/sub/index.d.ts", "")
+    .add_file("/app/node_modules/@types/node/fs.d.ts", "")
+    .add_file("/app/packages/web/node_modules/untyped/index.d.ts", "")
+    .add_file("/app/node_modules/broken/package.json", "{")
+    .add_file("/app/node_modules/foo/index.d.ts", "")
+    .add_file("/app/node_modules/x.d.ts", "")
+    .add_file("/app/node_modules/@types/events/index.d.ts", "");
+    fs
+}
+
+#[test]
+fn packages() {
+    let fs = project();
+    let resolver = DeclarationResolver::new(&fs);
+    let cases = [
+        (
+            "typings",
+            "/app/src",
+            Some("/app/node_modules/typings/lib/main.d.ts"),
+        ),
+        (
+            "types-dir",
+            "/app/src",
+            Some("/app/node_modules/types-dir/types/index.d.ts"),
+        ),
+        (
+            "from-main",
+            "/app/src",
+            Some("/app/node_modules/from-main/dist/main.d.ts"),
+        ),
+        (
+            "esm-main",
+            "/app/src",
+            Some("/app/node_modules/esm-main/dist/main.d.mts"),
+        ),
+        (
+            "index-only",
+            "/app",
+            Some("/app/node_modules/index-only/index.d.ts"),
+        ),
+        (
+            "index-only/sub",
+            "/app",
+            Some("/app/node_modules/index-only/sub.d.ts"),
+        ),
+        (
+            "index-only/sub.js",
+            "/app",
+            Some("/app/node_modules/index-only/sub.d.ts"),
+        ),
+        (
+            "index-only/sub.d.ts",
+            "/app",
+            Some("/app/node_modules/index-only/sub.d.ts"),
+        ),
+        (
+            "index-only/nested",
+            "/app",
+            Some("/app/node_modules/index-only/nested/index.d.ts"),
+        ),
+        (
+            "index-only/typed-dir",
+            "/app",
+            Some("/app/node_modules/index-only/typed-dir/main.d.ts"),
+        ),
+        (
+            "index-only/file.cjs",
+            "/app",
+            Some("/app/node_modules/index-only/file.d.cts"),
+        ),
+        ("index-only/missing", "/app", None),
+        (
+            "untyped",
+            "/app/src",
+            Some("/app/node_modules/@types/untyped/index.d.ts"),
+        ),
+        (
+            "untyped",
+            "/app/packages/web/src",
+            Some("/app/packages/web/node_modules/untyped/index.d.ts"),
+        ),
+        (
+            "@scope/pkg",
+            "/app/src",
+            Some("/node_modules/@types/scope__pkg/types.d.ts"),
+        ),
+        (
+            "@scope/pkg/sub",
+            "/app/src",
+            Some("/node_modules/@types/scope__pkg/sub/index.d.ts"),
+        ),
+        (
+            "@types/node/fs",
+            "/app/src",
+            Some("/app/node_modules/@types/node/fs.d.ts"),
+        ),
+        (
+            "untyped",
+            "/app/node_modules/typings",
+            Some("/app/node_modules/@types/untyped/index.d.ts"),
+        ),
+        ("nope", "/app/src", None),
+        // Only bare specifiers are packages. A builtin name without `node:` may still be one.
+        ("./foo", "/app", None),
+        ("../foo", "/app", None),
+        ("/x", "/app", None),
+        ("C:\\x", "/app", None),
+        ("file:///app/node_modules/foo/index.d.ts", "/app", None),
+        ("node:events", "/app", None),
+        (
+            "events",
+            "/app",
+            Some("/app/node_modules/@types/events/index.d.ts"),
+        ),
+        ("#foo", "/app", None),
+        (".foo", "/app", None),
+    ];
+    for (specifier, from_dir, expected) in cases {
+        assert_eq!(
+            resolver.resolve(specifier, from_dir).unwrap().as_deref(),
+            expected,
+            "{} from {}",
+            specifier,
+            from_dir
+        );
+    }
+
+    let err = resolver.resolve("broken", "/app").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn types_versions() {
+    let mut fs = MemoryFileSystem::new();
+    fs.add_file(
+        "/m/versioned/package.json",
+        r#"{
+            "types": "./index.d.ts",
+            "typesVersions": {
+                ">=5.0": { "*": ["ts5/*"] },
+                ">=4.2 <5": { "index.d.ts": ["ts4/main.d.ts"], "utils/*": ["ts4/utils/*", "ts4/fallback/*"], "*": ["ts4/*"] },
+                "<4.2 || 3.9": { "*": ["ts3/*"] }
+            }
+        }"#,
+    )
+    .add_file("/m/versioned/index.d.ts", "")
+    .add_file("/m/versioned/sub.d.ts", "")
+    .add_file("/m/versioned/ts5/index.d.ts", "")
+    .add_file("/m/versioned/ts5/sub.d.ts", "")
+    .add_file("/m/versioned/ts4/main.d.ts", "")
+    .add_file("/m/versioned/ts4/sub/index.d.ts", "")
+    .add_file("/m/versioned/ts4/fallback/a.d.ts", "")
+    .add_file("/m/versioned/ts3/index.d.ts", "")
+    .add_file("/m/untyped-entry/package.json", r#"{ "typesVersions": { "*": { "*": ["types/*"] } } }"#)
+    .add_file("/m/untyped-entry/types/index.d.ts", "")
+    .add_file("/m/unmatched/package.json", r#"{ "typesVersions": { "<3": { "*": ["old/*"] } } }"#)
+    .add_file("/m/unmatched/index.d.ts", "");
+
+    let cases = [
+        ((5, 4), "", Some("/m/versioned/ts5/index.d.ts")),
+        ((5, 4), "sub", Some("/m/versioned/ts5/sub.d.ts")),
+        ((5, 4), "missing", None),
+        ((4, 9), "", Some("/m/versioned/ts4/main.d.ts")),
+        ((4, 9), "sub", Some("/m/versioned/ts4/sub/index.d.ts")),
+        ((4, 2), "utils/a", Some("/m/versioned/ts4/fallback/a.d.ts")),
+        ((4, 1), "", Some("/m/versioned/ts3/index.d.ts")),
+        ((3, 9), "", Some("/m/versioned/ts3/index.d.ts")),
+        ((3, 9), "sub", Some("/m/versioned/sub.d.ts")),
+    ];
+    for ((major, minor), subpath, expected) in cases {
+        let resolver = DeclarationResolver::new(&fs).typescript_version(major, minor);
+        assert_eq!(
+            resolver
+                .resolve_package("/m/versioned", subpath)
+                .unwrap()
+                .as_deref(),
+            expected,
+            "{} with {}.{}",
+            subpath,
+            major,
+            minor
+        );
+    }
+
+    let resolver = DeclarationResolver::new(&fs);
+    assert_eq!(
+        resolver
+            .resolve_package("/m/untyped-entry", "")
+            .unwrap()
+            .as_deref(),
+        Some("/m/untyped-entry/types/index.d.ts")
+    );
+    assert_eq!(
+        resolver
+            .resolve_package("/m/unmatched", "")
+            .unwrap()
+            .as_deref(),
+        Some("/m/unmatched/index.d.ts")
+    );
+}
+
+#[test]
+fn types_versions_ranges() {
+    let cases = [
+        ("*", (1, 0), true),
+        ("4.2", (4, 2), true),
+        ("4.2", (4, 3), false),
+        ("4", (4, 9), true),
+        ("4.x", (4, 9), true),
+        ("4.2.x", (4, 2), true),
+        ("4.2.X", (4, 3), false),
+        ("=4.2", (4, 2), true),
+        (">=4.2", (4, 2), true),
+        (">=4.2", (4, 1), false),
+        (">4.2", (4, 2), false),
+        (">4.2", (4, 3), true),
+        (">4", (4, 9), false),
+        (">4", (5, 0), true),
+        (">4.2.0", (4, 2), false),
+        ("<4.2", (4, 2), false),
+        ("<4.2", (4, 1), true),
+        ("<=4.2", (4, 2), true),
+        ("<=4.2", (4, 3), false),
+        ("<=4", (4, 9), true),
+        ("<=4.2.0", (4, 2), true),
+        ("^4.2", (4, 9), true),
+        ("^4.2", (4, 1), false),
+        ("^4.2", (5, 0), false),
+        ("^0.2", (0, 2), true),
+        ("^0.2", (0, 3), false),
+        ("^0", (0, 9), true),
+        ("^0", (1, 0), false),
+        ("~4.2", (4, 2), true),
+        ("~4.2", (4, 3), false),
+        ("~4", (4, 9), true),
+        ("~4", (5, 0), false),
+        (">=4.2 <5", (4, 9), true),
+        (">=4.2 <5", (5, 0), false),
+        ("<3 || ^4.1", (4, 5), true),
+        ("<3 || ^4.1", (3, 5), false),
+        ("<*", (1, 0), false),
+        ("4.2-beta", (4, 2), false),
+    ];
+    for (range, (major, minor), matches) in cases {
+        let mut fs = MemoryFileSystem::new();
+        fs.add_file(
+            "/m/pkg/package.json",
+            &format!(
+                r#"{{ "typesVersions": {{ "{}": {{ "*": ["matched/*"] }} }} }}"#,
+                range
+            ),
+        )
+        .add_file("/m/pkg/index.d.ts", "")
+        .add_file("/m/pkg/matched/index.d.ts", "");
+        let resolver = DeclarationResolver::new(&fs).typescript_version(major, minor);
+        let expected = if matches {
+            "/m/pkg/matched/index.d.ts"
+        } else {
+            "/m/pkg/index.d.ts"
+        };
+        assert_eq!(
+            resolver.resolve_package("/m/pkg", "").unwrap().as_deref(),
+            Some(expected),
+            "{} with {}.{}",
+            range,
+            major,
+            minor
+        );
+    }
+}
+
+#[test]
+fn types_package_names() {
+    let cases = [
+        ("lodash", "@types/lodash"),
+        ("@babel/core", "@types/babel__core"),
+        ("@types/node", "@types/types__node"),
+        ("@scope", "@types/@scope"),
+    ];
+    for (name, expected) in cases {
+        assert_eq!(types_package_name(name), expected);
+    }
+}
@@ -12,3 +12,58 @@ fn test_posix() {
     assert_eq!(nodejs_path::posix::dirname("//a"), "//".to_string());
     assert_eq!(nodejs_path::posix::dirname("foo"), ".".to_string());
 }
+
+#[test]
+fn test_win32() {
+    let cases = [
+        ("c:\\", "c:\\"),
+        ("c:\\foo", "c:\\"),
+        ("c:\\foo\\", "c:\\"),
+        ("c:\\foo\\bar", "c:\\foo"),
+        ("c:\\foo\\bar\\", "c:\\foo"),
+        ("c:\\foo\\bar\\baz", "c:\\foo\\bar"),
+        ("c:\\foo bar\\baz", "c:\\foo bar"),
+        ("\\", "\\"),
+        ("\\foo", "\\"),
+        ("\\foo\\", "\\"),
+        ("\\foo\\bar", "\\foo"),
+        ("\\foo\\bar\\", "\\foo"),
+        ("\\foo\\bar\\baz", "\\foo\\bar"),
+        ("\\foo bar\\baz", "\\foo bar"),
+        ("c:", "c:"),
+        ("c:foo", "c:"),
+        ("c:foo\\", "c:"),
+        ("c:foo\\bar", "c:foo"),
+        ("c:foo\\bar\\", "c:foo"),
+        ("c:foo\\bar\\baz", "c:foo\\bar"),
+        ("c:foo bar\\baz", "c:foo bar"),
+        ("file:stream", "."),
+        ("dir\\file:stream", "dir"),
+        ("\\\\unc\\share", "\\\\unc\\share"),
+        ("\\\\unc\\share\\foo", "\\\\unc\\share\\"),
+        ("\\\\unc\\share\\foo\\", "\\\\unc\\share\\"),
+        ("\\\\unc\\share\\foo\\bar", "\\\\unc\\share\\foo"),
+        ("\\\\unc\\share\\foo\\bar\\", "\\\\unc\\share\\foo"),
+        ("\\\\unc\\share\\foo\\bar\\baz", "\\\\unc\\share\\foo\\bar"),
+        ("/a/b/", "/a"),
+        ("/a/b", "/a"),
+        ("/a", "/"),
+        ("", "."),
+        ("/", "/"),
+        ("////", "/"),
+        ("//a", "/"),
+        ("foo", "."),
+        ("//unc/share/x", "//unc/share/"),
+        ("\\\\\\x", "\\\\"),
+        ("\\\\x\\\\\\y", "\\\\x\\\\\\y"),
+        ("\\\\x", "\\"),
+        ("\\\\x\\", "\\"),
+        ("a", "."),
+        ("a\\", "."),
+        ("ñ\\ü\\x", "ñ\\ü"),
+        ("C:/x/", "C:/"),
+    ];
+    for (path, expected) in cases {
+        assert_eq!(nodejs_path::win32::dirname(path), expected, "{:?}", path);
+    }
+}
@@ -0,0 +1,104 @@
+use crate as nodejs_path;
+
+use std::io;
+
+use nodejs_path::resolver::{EntryResolver, MemoryFileSystem, PackageEntry};
+
+fn entry(path: &str, field: Option<&str>) -> Option<PackageEntry> {
+    Some(PackageEntry {
+        path: path.to_owned(),
+        field: field.map(str::to_owned),
+    })
+}
+
+fn packages() -> MemoryFileSystem {
+    let mut fs = MemoryFileSystem::new();
+    fs.add_file(
+        "/m/exact/package.json",
+        r#"{ "main": "lib/main.js", "module": "./lib/main.mjs", "browser": { "./lib/main.js": false } }"#,
+    )
+    .add_file("/m/exact/lib/main.js", "")
+    .add_file("/m/exact/lib/main.mjs", "")
+    .add_file("/m/ext/package.json", r#"{ "main": "./lib/main" }"#)
+    .add_file("/m/ext/lib/main.json", "{}")
+    .add_file("/m/ext/lib/main.node", "")
+    .add_file("/m/dir/package.json", r#"{ "main": "lib", "browser": "./lib/browser/" }"#)
+    .add_file("/m/dir/lib/index.json", "{}")
+    .add_file("/m/dir/lib/browser/index.js", "")
+    .add_file("/m/missing/package.json", r#"{ "main": "./nope.js", "module": "" }"#)
+    .add_file("/m/missing/index.node", "")
+    .add_file("/m/none/index.js", "")
+    .add_file("/m/empty/package.json", "{}")
+    .add_file("/m/broken/package.json", "{ main: }")
+    .add_file("/m/types/package.json", r#"{ "main": "./index" }"#)
+    .add_file("/m/types/index.ts", "")
+    .add_file("/m/types/index.d.ts", "")
+    .add_file("/m/types/main.ts", "");
+    fs
+}
+
+#[test]
+fn defaults() {
+    let fs = packages();
+    let resolver = EntryResolver::new(&fs);
+    let cases = [
+        ("/m/exact", entry("/m/exact/lib/main.js", Some("main"))),
+        ("/m/ext", entry("/m/ext/lib/main.json", Some("main"))),
+        ("/m/dir", entry("/m/dir/lib/index.json", Some("main"))),
+        ("/m/dir/", entry("/m/dir/lib/index.json", Some("main"))),
+        ("/m/missing", entry("/m/missing/index.node", None)),
+        ("/m/none", entry("/m/none/index.js", None)),
+        ("/m/empty", None),
+        ("/m/types", None),
+        ("/m/nope", None),
+    ];
+    for (dir, expected) in cases {
+        assert_eq!(resolver.resolve(dir).unwrap(), expected, "{}", dir);
+    }
+
+    let err = resolver.resolve("/m/broken").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    assert!(err.to_string().starts_with("/m/broken/package.json: "));
+}
+
+#[test]
+fn configured() {
+    let fs = packages();
+    let bundler = EntryResolver::new(&fs).main_fields(["browser", "module", "main"]);
+    let cases = [
+        ("/m/exact", entry("/m/exact/lib/main.mjs", Some("module"))),
+        (
+            "/m/dir",
+            entry("/m/dir/lib/browser/index.js", Some("browser")),
+        ),
+        ("/m/ext", entry("/m/ext/lib/main.json", Some("main"))),
+    ];
+    for (dir, expected) in cases {
+        assert_eq!(bundler.resolve(dir).unwrap(), expected, "{}", dir);
+    }
+
+    let typescript = EntryResolver::new(&fs)
+        .main_fields(["types", "main"])
+        .main_files(["main", "index"])
+        .extensions([".d.ts", ".ts"]);
+    assert_eq!(
+        typescript.resolve("/m/types").unwrap(),
+        entry("/m/types/index.d.ts", Some("main"))
+    );
+
+    let no_fields = EntryResolver::new(&fs)
+        .main_fields(Vec::<String>::new())
+        .main_files(["main", "index"])
+        .extensions([".ts"]);
+    assert_eq!(
+        no_fields.resolve("/m/types").unwrap(),
+        entry("/m/types/main.ts", None)
+    );
+
+    let no_extensions = EntryResolver::new(&fs).extensions(Vec::<String>::new());
+    assert_eq!(
+        no_extensions.resolve("/m/exact").unwrap(),
+        entry("/m/exact/lib/main.js", Some("main"))
+    );
+    assert_eq!(no_extensions.resolve("/m/ext").unwrap(), None);
+}
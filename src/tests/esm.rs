@@ -0,0 +1,431 @@
+use crate as nodejs_path;
+
+use nodejs_path::resolver::{
+    file_url_to_path, path_to_file_url, BuiltinModules, EsmError, EsmResolver, MemoryFileSystem,
+    NodeVersion, OsFileSystem,
+};
+
+const PARENT: &str = "file:///app/src/index.js";
+
+fn project() -> MemoryFileSystem {
+    let mut fs = MemoryFileSystem::new();
+    fs.add_file(
+        "/app/package.json",
+        r##"{
+            "name": "app",
+            "exports": { ".": "./src/index.js", "./feature": "./src/feature.js" },
+            "imports": {
+                "#internal/*": "./src/internal/*.js",
+                "#internal/special": "./src/special.js",
+                "#dep": "dep",
+                "#dep/*": "dep/utils/*",
+                "#cond": { "custom": "./src/custom.js", "node": "./src/node.js", "default": "./src/default.js" },
+                "#escape": "../outside.js"
+            }
+        }"##,
+    )
+    .add_file("/app/src/index.js", "")
+    .add_file("/app/src/main.js", "")
+    .add_file("/app/src/feature.js", "")
+    .add_file("/app/src/a b.js", "")
+    .add_file("/app/src/internal/x.js", "")
+    .add_file("/app/src/special.js", "")
+    .add_file("/app/src/node.js", "")
+    .add_file("/app/src/custom.js", "")
+    .add_dir("/app/src/dir")
+    .add_file(
+        "/app/node_modules/dep/package.json",
+        r#"{
+            "exports": {
+                ".": { "require": "./cjs/index.js", "import": "./esm/index.js" },
+                "./utils/*": "./esm/utils/*.js",
+                "./utils/private/*": null,
+                "./utils/*/deep.js": "./esm/deep/*.js",
+                "./fallback": ["../nope.js", "./esm/index.js"],
+                "./escape": "../outside.js",
+                "./nested": "./node_modules/x.js",
+                "./array": [],
+                "./number": 1
+            }
+        }"#,
+    )
+    .add_file("/app/node_modules/dep/esm/index.js", "")
+    .add_file("/app/node_modules/dep/esm/utils/x.js", "")
+    .add_file("/app/node_modules/dep/esm/deep/a.js", "")
+    .add_file("/app/node_modules/legacy/package.json", r#"{ "main": "lib/main" }"#)
+    .add_file("/app/node_modules/legacy/lib/main.js", "")
+    .add_file("/app/node_modules/legacy/lib/other.js", "")
+    .add_file("/app/node_modules/noindex/index.js", "")
+    .add_dir("/app/node_modules/empty")
+    .add_file("/app/node_modules/@scope/pkg/package.json", r#"{ "exports": "./index.js" }"#)
+    .add_file("/app/node_modules/@scope/pkg/index.js", "")
+    .add_file(
+        "/app/node_modules/mixed/package.json",
+        r#"{ "exports": { ".": "./a.js", "import": "./b.js" } }"#,
+    )
+    .add_file(
+        "/app/node_modules/numeric/package.json",
+        r#"{ "exports": { "0": "./a.js" } }"#,
+    )
+    .add_file("/app/node_modules/broken/package.json", "{")
+    .add_symlink("/app/node_modules/linked", "../packages/linked")
+    .add_file("/app/packages/linked/package.json", r#"{ "exports": "./index.js" }"#)
+    .add_file("/app/packages/linked/index.js", "")
+    .add_file("/node_modules/global/index.js", "");
+    fs
+}
+
+#[test]
+fn relative_and_absolute() {
+    let fs = project();
+    let resolver = EsmResolver::new(&fs);
+    let cases = [
+        ("./main.js", "file:///app/src/main.js"),
+        ("../src/./main.js", "file:///app/src/main.js"),
+        ("./main.js?query#hash", "file:///app/src/main.js?query#hash"),
+        ("./a b.js", "file:///app/src/a%20b.js"),
+        ("./a%20b.js", "file:///app/src/a%20b.js"),
+        ("./%2e%2E/src/%2e/main.js", "file:///app/src/main.js"),
+        (
+            "file:///app/x/%2e%2e/src/main.js",
+            "file:///app/src/main.js",
+        ),
+        ("/app/src/main.js", "file:///app/src/main.js"),
+        ("file:///app/src/main.js", "file:///app/src/main.js"),
+        (
+            "file://localhost/app/x/../src/main.js#1",
+            "file:///app/src/main.js#1",
+        ),
+        (
+            "data:text/javascript,export default 1",
+            "data:text/javascript,export default 1",
+        ),
+    ];
+    for (specifier, expected) in cases {
+        assert_eq!(
+            resolver.resolve(specifier, PARENT).unwrap(),
+            expected,
+            "{:?}",
+            specifier
+        );
+    }
+
+    let code = |specifier| resolver.resolve(specifier, PARENT).unwrap_err().code();
+    // Extensions and index files are not searched.
+    assert_eq!(code("./main"), "ERR_MODULE_NOT_FOUND");
+    assert_eq!(code("./dir"), "ERR_UNSUPPORTED_DIR_IMPORT");
+    assert_eq!(code("./dir/"), "ERR_UNSUPPORTED_DIR_IMPORT");
+    assert_eq!(code("."), "ERR_UNSUPPORTED_DIR_IMPORT");
+    assert_eq!(code("./a%2Fb.js"), "ERR_INVALID_MODULE_SPECIFIER");
+    assert_eq!(code("./a%5cb.js"), "ERR_INVALID_MODULE_SPECIFIER");
+    // Only `/` makes a specifier relative, so this is a package name starting with `.`.
+    assert_eq!(code(".\\main.js"), "ERR_INVALID_MODULE_SPECIFIER");
+    assert_eq!(
+        code("https://example.com/a.js"),
+        "ERR_UNSUPPORTED_ESM_URL_SCHEME"
+    );
+    assert_eq!(code("file://server/a.js"), "ERR_INVALID_URL");
+
+    assert_eq!(
+        resolver
+            .resolve("./main.js", "https://example.com/")
+            .unwrap_err(),
+        EsmError::InvalidUrl {
+            url: "https://example.com/".to_owned()
+        }
+    );
+}
+
+#[test]
+fn builtins() {
+    let fs = project();
+    let resolver = EsmResolver::new(&fs);
+    assert_eq!(resolver.resolve("fs", PARENT).unwrap(), "node:fs");
+    assert_eq!(
+        resolver.resolve("fs/promises", PARENT).unwrap(),
+        "node:fs/promises"
+    );
+    assert_eq!(resolver.resolve("node:path", PARENT).unwrap(), "node:path");
+    assert_eq!(resolver.resolve("node:test", PARENT).unwrap(), "node:test");
+    // `test` is only a builtin with the prefix.
+    assert_eq!(
+        resolver.resolve("test", PARENT).unwrap_err().code(),
+        "ERR_MODULE_NOT_FOUND"
+    );
+    assert_eq!(
+        resolver.resolve("node:nope", PARENT).unwrap_err(),
+        EsmError::UnknownBuiltinModule {
+            specifier: "node:nope".to_owned()
+        }
+    );
+    assert_eq!(
+        resolver.resolve("NODE:fs", PARENT).unwrap_err(),
+        EsmError::UnknownBuiltinModule {
+            specifier: "NODE:fs".to_owned()
+        }
+    );
+
+    let node14 = EsmResolver::new(&fs)
+        .with_builtins(BuiltinModules::for_version(NodeVersion::new(14, 0, 0)));
+    assert_eq!(node14.resolve("fs", PARENT).unwrap(), "node:fs");
+    assert_eq!(
+        node14.resolve("node:test", PARENT).unwrap_err().code(),
+        "ERR_UNKNOWN_BUILTIN_MODULE"
+    );
+}
+
+#[test]
+fn packages() {
+    let fs = project();
+    let resolver = EsmResolver::new(&fs);
+    let cases = [
+        ("dep", "file:///app/node_modules/dep/esm/index.js"),
+        ("dep/utils/x", "file:///app/node_modules/dep/esm/utils/x.js"),
+        // The longer pattern base wins.
+        (
+            "dep/utils/a/deep.js",
+            "file:///app/node_modules/dep/esm/deep/a.js",
+        ),
+        ("dep/fallback", "file:///app/node_modules/dep/esm/index.js"),
+        ("legacy", "file:///app/node_modules/legacy/lib/main.js"),
+        (
+            "legacy/lib/other.js",
+            "file:///app/node_modules/legacy/lib/other.js",
+        ),
+        ("noindex", "file:///app/node_modules/noindex/index.js"),
+        ("@scope/pkg", "file:///app/node_modules/@scope/pkg/index.js"),
+        ("global/index.js", "file:///node_modules/global/index.js"),
+        // Self-reference through the package's own `exports`.
+        ("app", "file:///app/src/index.js"),
+        ("app/feature", "file:///app/src/feature.js"),
+    ];
+    for (specifier, expected) in cases {
+        assert_eq!(
+            resolver.resolve(specifier, PARENT).unwrap(),
+            expected,
+            "{:?}",
+            specifier
+        );
+    }
+
+    let code = |specifier| resolver.resolve(specifier, PARENT).unwrap_err().code();
+    assert_eq!(code("dep/utils/private/x"), "ERR_PACKAGE_PATH_NOT_EXPORTED");
+    assert_eq!(code("dep/missing"), "ERR_PACKAGE_PATH_NOT_EXPORTED");
+    assert_eq!(code("dep/array"), "ERR_PACKAGE_PATH_NOT_EXPORTED");
+    assert_eq!(code("dep/esm/index.js"), "ERR_PACKAGE_PATH_NOT_EXPORTED");
+    assert_eq!(code("dep/escape"), "ERR_INVALID_PACKAGE_TARGET");
+    assert_eq!(code("dep/nested"), "ERR_INVALID_PACKAGE_TARGET");
+    assert_eq!(code("dep/number"), "ERR_INVALID_PACKAGE_TARGET");
+    assert_eq!(code("dep/utils/../x"), "ERR_INVALID_MODULE_SPECIFIER");
+    assert_eq!(code("@scope/pkg/index.js"), "ERR_PACKAGE_PATH_NOT_EXPORTED");
+    assert_eq!(code("@scope"), "ERR_INVALID_MODULE_SPECIFIER");
+    assert_eq!(code(".hidden"), "ERR_INVALID_MODULE_SPECIFIER");
+    assert_eq!(code("a%20b"), "ERR_INVALID_MODULE_SPECIFIER");
+    assert_eq!(code("mixed"), "ERR_INVALID_PACKAGE_CONFIG");
+    assert_eq!(code("numeric"), "ERR_INVALID_PACKAGE_CONFIG");
+    assert_eq!(code("broken"), "ERR_INVALID_PACKAGE_CONFIG");
+    assert_eq!(code("empty"), "ERR_MODULE_NOT_FOUND");
+    assert_eq!(code("app/missing"), "ERR_PACKAGE_PATH_NOT_EXPORTED");
+
+    assert_eq!(
+        resolver.resolve("missing", PARENT).unwrap_err(),
+        EsmError::PackageNotFound {
+            package: "missing".to_owned(),
+            base: "/app/src/index.js".to_owned()
+        }
+    );
+    // A package without `exports` and no main file reports its directory, like Node 22.
+    assert_eq!(
+        resolver.resolve("empty", PARENT).unwrap_err(),
+        EsmError::PackageNotFound {
+            package: "/app/node_modules/empty/".to_owned(),
+            base: "/app/src/index.js".to_owned()
+        }
+    );
+    assert_eq!(
+        resolver
+            .resolve("dep/missing", PARENT)
+            .unwrap_err()
+            .to_string(),
+        "Package subpath './missing' is not defined by \"exports\" in \
+         /app/node_modules/dep/package.json imported from /app/src/index.js"
+    );
+    assert_eq!(
+        resolver.resolve("dep/escape", PARENT).unwrap_err().to_string(),
+        "Invalid \"exports\" target \"../outside.js\" defined for './escape' in the package config \
+         /app/node_modules/dep/package.json imported from /app/src/index.js; targets must start with \"./\""
+    );
+}
+
+#[test]
+fn package_imports() {
+    let fs = project();
+    let resolver = EsmResolver::new(&fs);
+    let cases = [
+        ("#internal/x", "file:///app/src/internal/x.js"),
+        ("#internal/special", "file:///app/src/special.js"),
+        ("#dep", "file:///app/node_modules/dep/esm/index.js"),
+        ("#dep/x", "file:///app/node_modules/dep/esm/utils/x.js"),
+        ("#cond", "file:///app/src/node.js"),
+    ];
+    for (specifier, expected) in cases {
+        assert_eq!(
+            resolver.resolve(specifier, PARENT).unwrap(),
+            expected,
+            "{:?}",
+            specifier
+        );
+    }
+
+    let code = |specifier| resolver.resolve(specifier, PARENT).unwrap_err().code();
+    assert_eq!(code("#missing"), "ERR_PACKAGE_IMPORT_NOT_DEFINED");
+    assert_eq!(code("#"), "ERR_INVALID_MODULE_SPECIFIER");
+    assert_eq!(code("#/x"), "ERR_INVALID_MODULE_SPECIFIER");
+    assert_eq!(code("#escape"), "ERR_INVALID_PACKAGE_TARGET");
+    assert_eq!(code("#internal/y"), "ERR_MODULE_NOT_FOUND");
+
+    // `imports` don't cross into `node_modules` packages.
+    assert_eq!(
+        resolver
+            .resolve("#internal/x", "file:///app/node_modules/dep/esm/index.js")
+            .unwrap_err(),
+        EsmError::PackageImportNotDefined {
+            specifier: "#internal/x".to_owned(),
+            package_json: Some("/app/node_modules/dep/package.json".to_owned()),
+            base: "/app/node_modules/dep/esm/index.js".to_owned()
+        }
+    );
+}
+
+#[test]
+fn conditions() {
+    let fs = project();
+    let resolver = EsmResolver::new(&fs).with_conditions(["custom"]);
+    assert_eq!(
+        resolver.resolve("#cond", PARENT).unwrap(),
+        "file:///app/src/custom.js"
+    );
+    assert_eq!(
+        resolver.resolve("dep", PARENT).unwrap(),
+        "file:///app/node_modules/dep/esm/index.js"
+    );
+
+    // A condition whose array has no matching target falls through to the next condition.
+    let mut fs = MemoryFileSystem::new();
+    fs.add_file(
+        "/app/node_modules/fallback/package.json",
+        r#"{ "exports": { ".": { "node": [{ "require": "./a.cjs" }], "default": "./b.js" } } }"#,
+    )
+    .add_file("/app/node_modules/fallback/a.cjs", "")
+    .add_file("/app/node_modules/fallback/b.js", "")
+    .add_file(
+        "/app/node_modules/excluded/package.json",
+        r#"{ "exports": { ".": { "node": [{ "require": "./a.cjs" }, null], "default": "./b.js" } } }"#,
+    )
+    .add_file("/app/node_modules/excluded/b.js", "");
+    assert_eq!(
+        EsmResolver::new(&fs).resolve("fallback", PARENT).unwrap(),
+        "file:///app/node_modules/fallback/b.js"
+    );
+    assert_eq!(
+        EsmResolver::new(&fs)
+            .resolve("excluded", PARENT)
+            .unwrap_err()
+            .code(),
+        "ERR_PACKAGE_PATH_NOT_EXPORTED"
+    );
+
+    // `node-addons` matches unless addons are turned off, like `--no-addons`.
+    let mut fs = MemoryFileSystem::new();
+    fs.add_file(
+        "/app/node_modules/addon/package.json",
+        r#"{ "exports": { "node-addons": "./native.js", "default": "./js.js" } }"#,
+    )
+    .add_file("/app/node_modules/addon/native.js", "")
+    .add_file("/app/node_modules/addon/js.js", "");
+    assert_eq!(
+        EsmResolver::new(&fs).resolve("addon", PARENT).unwrap(),
+        "file:///app/node_modules/addon/native.js"
+    );
+    assert_eq!(
+        EsmResolver::new(&fs)
+            .addons(false)
+            .resolve("addon", PARENT)
+            .unwrap(),
+        "file:///app/node_modules/addon/js.js"
+    );
+}
+
+#[test]
+fn symlinks() {
+    let fs = project();
+    assert_eq!(
+        EsmResolver::new(&fs).resolve("linked", PARENT).unwrap(),
+        "file:///app/packages/linked/index.js"
+    );
+    assert_eq!(
+        EsmResolver::new(&fs)
+            .preserve_symlinks(true)
+            .resolve("linked", PARENT)
+            .unwrap(),
+        "file:///app/node_modules/linked/index.js"
+    );
+}
+
+#[test]
+fn os_file_system() {
+    let root = std::env::temp_dir().join(format!("nodejs_path_esm_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&root);
+    std::fs::create_dir_all(root.join("src")).unwrap();
+    std::fs::write(root.join("src/a.js"), "").unwrap();
+    let root = std::fs::canonicalize(&root).unwrap();
+    let root = root.to_str().unwrap();
+
+    let parent = path_to_file_url(&format!("{}/src/index.js", root));
+    let resolved = EsmResolver::new(OsFileSystem)
+        .resolve("./a.js", &parent)
+        .unwrap();
+    assert_eq!(
+        file_url_to_path(&resolved).unwrap(),
+        format!("{}/src/a.js", root)
+    );
+    assert_eq!(
+        EsmResolver::new(OsFileSystem)
+            .resolve("./b.js", &parent)
+            .unwrap_err()
+            .code(),
+        "ERR_MODULE_NOT_FOUND"
+    );
+    std::fs::remove_dir_all(root).unwrap();
+}
+
+#[test]
+fn file_urls() {
+    let cases = [
+        ("/", "file:///"),
+        ("/a/b.js", "file:///a/b.js"),
+        ("/a/b/", "file:///a/b/"),
+        ("/a b/%/#?.js", "file:///a%20b/%25/%23%3F.js"),
+        ("/a\\b", "file:///a%5Cb"),
+        ("/é/🦀", "file:///%C3%A9/%F0%9F%A6%80"),
+        ("/a/../b", "file:///b"),
+    ];
+    for (path, url) in cases {
+        assert_eq!(path_to_file_url(path), url, "{:?}", path);
+        assert_eq!(
+            file_url_to_path(url).unwrap(),
+            nodejs_path::posix::normalize(path),
+            "{:?}",
+            url
+        );
+    }
+
+    assert_eq!(file_url_to_path("file:/a/b").unwrap(), "/a/b");
+    assert_eq!(file_url_to_path("FILE:///a/b#c").unwrap(), "/a/b");
+    assert_eq!(file_url_to_path("file:///a/%2e%2e/b").unwrap(), "/b");
+    assert_eq!(file_url_to_path("file:///a/.%2E/b/%2e").unwrap(), "/b/");
+    assert_eq!(file_url_to_path("file:///a/%2e%2e%2e").unwrap(), "/a/...");
+    assert_eq!(file_url_to_path("file:///a%2Fb"), None);
+    assert_eq!(file_url_to_path("file:///a%zz"), None);
+    assert_eq!(file_url_to_path("file:///a%ff"), None);
+}
@@ -0,0 +1,90 @@
+use crate as nodejs_path;
+
+use nodejs_path::resolver::{ExtensionAlias, MemoryFileSystem};
+
+#[test]
+fn candidates() {
+    let alias = ExtensionAlias::typescript();
+    let cases: [(&str, &[&str]); 10] = [
+        (
+            "./foo.js",
+            &["./foo.ts", "./foo.tsx", "./foo.js", "./foo.jsx"],
+        ),
+        ("./foo.jsx", &["./foo.tsx", "./foo.jsx"]),
+        ("/a/b.c.mjs", &["/a/b.c.mts", "/a/b.c.mjs"]),
+        ("b.cjs", &["b.cts", "b.cjs"]),
+        ("./foo.ts", &["./foo.ts"]),
+        ("./foo.d.ts", &["./foo.d.ts"]),
+        ("./foo", &["./foo"]),
+        ("./.js", &["./.js"]),
+        ("./dir.js/", &["./dir.js/"]),
+        ("./FOO.JS", &["./FOO.JS"]),
+    ];
+    for (path, expected) in cases {
+        assert_eq!(alias.candidates(path), expected, "{}", path);
+    }
+
+    assert_eq!(ExtensionAlias::new().candidates("./foo.js"), ["./foo.js"]);
+    let replaced = ExtensionAlias::typescript().alias(".js", [".ts"]);
+    assert_eq!(replaced.candidates("./foo.js"), ["./foo.ts"]);
+    let dedup = ExtensionAlias::new().alias(".js", [".ts", ".js", ".ts"]);
+    assert_eq!(dedup.candidates("./foo.js"), ["./foo.ts", "./foo.js"]);
+}
+
+#[test]
+fn declarations() {
+    let alias = ExtensionAlias::typescript().declarations(true);
+    let cases: [(&str, &[&str]); 5] = [
+        (
+            "./foo.js",
+            &[
+                "./foo.ts",
+                "./foo.tsx",
+                "./foo.d.ts",
+                "./foo.js",
+                "./foo.jsx",
+            ],
+        ),
+        ("./foo.jsx", &["./foo.tsx", "./foo.d.ts", "./foo.jsx"]),
+        ("./foo.mjs", &["./foo.mts", "./foo.d.mts", "./foo.mjs"]),
+        ("./foo.cjs", &["./foo.cts", "./foo.d.cts", "./foo.cjs"]),
+        ("./foo.json", &["./foo.json"]),
+    ];
+    for (path, expected) in cases {
+        assert_eq!(alias.candidates(path), expected, "{}", path);
+    }
+
+    let only_typescript = ExtensionAlias::new()
+        .alias(".js", [".ts", ".mts"])
+        .declarations(true);
+    assert_eq!(
+        only_typescript.candidates("./foo.js"),
+        ["./foo.ts", "./foo.mts", "./foo.d.ts", "./foo.d.mts"]
+    );
+}
+
+#[test]
+fn find() {
+    let mut fs = MemoryFileSystem::new();
+    fs.add_file("/app/src/a.tsx", "")
+        .add_file("/app/src/a.js", "")
+        .add_file("/app/types/b.d.ts", "")
+        .add_file("/app/types/b.js", "")
+        .add_dir("/app/src/c.ts");
+    let alias = ExtensionAlias::typescript().declarations(true);
+    assert_eq!(
+        alias.find(&fs, "/app/src/a.js").as_deref(),
+        Some("/app/src/a.tsx")
+    );
+    assert_eq!(
+        alias.find(&fs, "/app/types/b.js").as_deref(),
+        Some("/app/types/b.d.ts")
+    );
+    assert_eq!(alias.find(&fs, "/app/src/c.js"), None);
+    assert_eq!(
+        ExtensionAlias::typescript()
+            .find(&fs, "/app/types/b.js")
+            .as_deref(),
+        Some("/app/types/b.js")
+    );
+}
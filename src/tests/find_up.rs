@@ -0,0 +1,176 @@
+use crate as nodejs_path;
+
+use std::cell::RefCell;
+
+use nodejs_path::{find_up, posix, FindUpOptions};
+
+const POSIX: &[&str] = &[
+    "/.editorconfig",
+    "/repo/.editorconfig",
+    "/repo/.eslintrc.json",
+    "/repo/package.json",
+    "/repo/packages/app/.eslintrc.js",
+    "/repo/packages/app/package.json",
+];
+
+fn posix_exists(path: &str) -> bool {
+    POSIX.contains(&path)
+}
+
+#[test]
+fn first() {
+    let eslintrc = [".eslintrc.js", ".eslintrc.cjs", ".eslintrc.json"];
+    let options = FindUpOptions::new().exists(posix_exists);
+    let cases: [(&str, &[&str], &[&str]); 7] = [
+        (
+            "/repo/packages/app/src/components",
+            &eslintrc,
+            &["/repo/packages/app/.eslintrc.js"],
+        ),
+        ("/repo/packages/lib", &eslintrc, &["/repo/.eslintrc.json"]),
+        (
+            "/repo/packages/app/",
+            &["package.json"],
+            &["/repo/packages/app/package.json"],
+        ),
+        (
+            "/repo/packages/app",
+            &[".editorconfig"],
+            &["/repo/.editorconfig"],
+        ),
+        ("/elsewhere", &[".editorconfig"], &["/.editorconfig"]),
+        ("/", &[".editorconfig"], &["/.editorconfig"]),
+        ("/repo", &["tsconfig.json"], &[]),
+    ];
+    for (start, names, expected) in cases {
+        assert_eq!(find_up(start, names, &options), expected, "{}", start);
+    }
+}
+
+#[test]
+fn all_and_stop() {
+    let all = FindUpOptions::new().all(true).exists(posix_exists);
+    assert_eq!(
+        find_up(
+            "/repo/packages/app/src",
+            &["package.json", ".editorconfig"],
+            &all
+        ),
+        [
+            "/repo/packages/app/package.json",
+            "/repo/package.json",
+            "/repo/.editorconfig",
+            "/.editorconfig"
+        ]
+    );
+
+    let stopped = FindUpOptions::new()
+        .all(true)
+        .stop_at("/repo/")
+        .exists(posix_exists);
+    assert_eq!(
+        find_up("/repo/packages/app/src", &[".editorconfig"], &stopped),
+        ["/repo/.editorconfig"]
+    );
+    assert_eq!(
+        find_up("/repo", &[".editorconfig"], &stopped),
+        ["/repo/.editorconfig"]
+    );
+    // A stop directory that isn't an ancestor doesn't stop anything.
+    let unrelated = FindUpOptions::new().stop_at("/other").exists(posix_exists);
+    assert_eq!(
+        find_up("/repo/packages", &[".editorconfig"], &unrelated),
+        ["/repo/.editorconfig"]
+    );
+}
+
+#[test]
+fn walk() {
+    let checked = RefCell::new(Vec::new());
+    let options = FindUpOptions::new().all(true).exists(|path: &str| {
+        checked.borrow_mut().push(path.to_owned());
+        false
+    });
+    assert!(find_up("/a/b//c/", &["x", "y"], &options).is_empty());
+    assert_eq!(
+        checked.take(),
+        ["/a/b/c/x", "/a/b/c/y", "/a/b/x", "/a/b/y", "/a/x", "/a/y", "/x", "/y"]
+    );
+
+    // Relative starts and stop directories are resolved against the process cwd, and the walk reaches the root.
+    let cwd = posix::cwd();
+    assert!(find_up("a/b", &["x"], &options).is_empty());
+    let walked = checked.take();
+    assert_eq!(
+        walked[..2],
+        [format!("{}/a/b/x", cwd), format!("{}/a/x", cwd)]
+    );
+    assert_eq!(walked.last().map(String::as_str), Some("/x"));
+
+    let stopped = FindUpOptions::new()
+        .stop_at(".")
+        .exists(|path: &str| path == "/x" || path == format!("{}/x", cwd));
+    assert_eq!(find_up("a/b", &["x"], &stopped), [format!("{}/x", cwd)]);
+    assert!(find_up("a/b", &["y"], &stopped).is_empty());
+
+    assert!(find_up("a", &[] as &[&str], &options).is_empty());
+    assert!(checked.take().is_empty());
+}
+
+#[test]
+fn win32() {
+    let files = [
+        "C:\\repo\\tsconfig.json",
+        "C:\\repo\\packages\\app\\tsconfig.json",
+        "\\\\server\\share\\tsconfig.json",
+    ];
+    let options = FindUpOptions::new()
+        .win32(true)
+        .all(true)
+        .exists(|path: &str| files.contains(&path));
+    assert_eq!(
+        find_up("C:\\repo\\packages\\app\\src", &["tsconfig.json"], &options),
+        [
+            "C:\\repo\\packages\\app\\tsconfig.json",
+            "C:\\repo\\tsconfig.json"
+        ]
+    );
+    assert_eq!(
+        find_up("C:\\repo\\packages\\lib\\", &["tsconfig.json"], &options),
+        ["C:\\repo\\tsconfig.json"]
+    );
+    assert_eq!(
+        find_up("\\\\server\\share\\dir\\sub", &["tsconfig.json"], &options),
+        ["\\\\server\\share\\tsconfig.json"]
+    );
+
+    let stopped = FindUpOptions::new()
+        .win32(true)
+        .stop_at("C:\\repo\\packages\\")
+        .exists(|path: &str| files.contains(&path));
+    assert!(find_up("C:\\repo\\packages\\lib", &["tsconfig.json"], &stopped).is_empty());
+
+    // Without a cwd to resolve against, a relative win32 start is only walked up to `.`.
+    let checked = RefCell::new(Vec::new());
+    let options = FindUpOptions::new()
+        .win32(true)
+        .all(true)
+        .exists(|path: &str| {
+            checked.borrow_mut().push(path.to_owned());
+            false
+        });
+    assert!(find_up("a\\b", &["x"], &options).is_empty());
+    assert_eq!(checked.take(), ["a\\b\\x", "a\\x", ".\\x"]);
+}
+
+#[test]
+fn real_filesystem() {
+    let manifest_dir = env!("CARGO_MANIFEST_DIR");
+    let start = format!("{}/src/tests", manifest_dir);
+    assert_eq!(
+        find_up(&start, &["Cargo.toml"], &FindUpOptions::new()),
+        [format!("{}/Cargo.toml", manifest_dir)]
+    );
+    let stopped = FindUpOptions::new().stop_at(&format!("{}/src", manifest_dir));
+    assert!(find_up(&start, &["Cargo.toml"], &stopped).is_empty());
+}
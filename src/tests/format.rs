@@ -0,0 +1,105 @@
+use crate as nodejs_path;
+
+use std::io;
+
+use nodejs_path::resolver::{
+    DefaultType, FormatDetector, MemoryFileSystem, ModuleFormat, PackageScopes,
+};
+
+fn project() -> MemoryFileSystem {
+    let mut fs = MemoryFileSystem::new();
+    fs.add_file("/esm/package.json", r#"{ "type": "module" }"#)
+        .add_file("/esm/cjs/package.json", r#"{ "type": "commonjs" }"#)
+        .add_file("/untyped/package.json", r#"{ "name": "untyped" }"#)
+        .add_file("/invalid/package.json", r#"{ "type": "Module" }"#)
+        .add_file("/esm/node_modules/dep/package.json", "{}")
+        .add_file(
+            "/esm/node_modules/esm-dep/package.json",
+            r#"{ "type": "module" }"#,
+        )
+        .add_file("/broken/package.json", "[");
+    fs
+}
+
+#[test]
+fn formats() {
+    let fs = project();
+    let formats = FormatDetector::new(&fs);
+    let cases = [
+        ("/esm/a.js", Some(ModuleFormat::Module)),
+        ("/esm/bin/cli", Some(ModuleFormat::Module)),
+        ("/esm/a.mjs", Some(ModuleFormat::Module)),
+        ("/esm/a.cjs", Some(ModuleFormat::CommonJs)),
+        ("/esm/cjs/a.js", Some(ModuleFormat::CommonJs)),
+        ("/esm/cjs/a.mjs", Some(ModuleFormat::Module)),
+        ("/untyped/a.js", Some(ModuleFormat::CommonJs)),
+        ("/invalid/a.js", Some(ModuleFormat::CommonJs)),
+        ("/loose/a.js", Some(ModuleFormat::CommonJs)),
+        ("/esm/node_modules/dep/a.js", Some(ModuleFormat::CommonJs)),
+        ("/esm/node_modules/esm-dep/a.js", Some(ModuleFormat::Module)),
+        ("/esm/data.json", Some(ModuleFormat::Json)),
+        ("/esm/lib.wasm", Some(ModuleFormat::Wasm)),
+        ("/esm/addon.node", Some(ModuleFormat::Addon)),
+        ("/esm/a.ts", None),
+        ("/esm/a.MJS", None),
+        ("/esm/.js", Some(ModuleFormat::Module)),
+        ("/broken/a.mjs", Some(ModuleFormat::Module)),
+    ];
+    for (path, expected) in cases {
+        assert_eq!(
+            formats.detect_module_format(path).unwrap(),
+            expected,
+            "{}",
+            path
+        );
+    }
+
+    let err = formats.detect_module_format("/broken/a.js").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn default_type() {
+    let fs = project();
+    let formats =
+        FormatDetector::with_scopes(PackageScopes::new(&fs)).default_type(DefaultType::Module);
+    let cases = [
+        ("/loose/a.js", Some(ModuleFormat::Module)),
+        ("/loose/script", Some(ModuleFormat::Module)),
+        ("/untyped/a.js", Some(ModuleFormat::Module)),
+        ("/invalid/a.js", Some(ModuleFormat::Module)),
+        ("/esm/cjs/a.js", Some(ModuleFormat::CommonJs)),
+        ("/loose/a.cjs", Some(ModuleFormat::CommonJs)),
+        ("/esm/node_modules/dep/a.js", Some(ModuleFormat::CommonJs)),
+        ("/loose/node_modules/a.js", Some(ModuleFormat::CommonJs)),
+        ("/esm/node_modules/esm-dep/a.js", Some(ModuleFormat::Module)),
+    ];
+    for (path, expected) in cases {
+        assert_eq!(
+            formats.detect_module_format(path).unwrap(),
+            expected,
+            "{}",
+            path
+        );
+    }
+
+    let scope = formats
+        .scopes()
+        .find_package_scope("/untyped/a.js")
+        .unwrap();
+    assert_eq!(scope.unwrap().name(), Some("untyped"));
+}
+
+#[test]
+fn display() {
+    let names = [
+        (ModuleFormat::Module, "module"),
+        (ModuleFormat::CommonJs, "commonjs"),
+        (ModuleFormat::Json, "json"),
+        (ModuleFormat::Wasm, "wasm"),
+        (ModuleFormat::Addon, "addon"),
+    ];
+    for (format, name) in names {
+        assert_eq!(format.to_string(), name);
+    }
+}
@@ -0,0 +1,126 @@
+use crate as nodejs_path;
+
+use std::{sync::Arc, thread};
+
+use nodejs_path::{posix, PathInterner};
+
+#[test]
+fn intern() {
+    let paths = PathInterner::new();
+    let id = paths.intern("/foo/bar/");
+    assert_eq!(paths.intern("/foo//bar"), id);
+    assert_eq!(paths.intern("/foo/baz/../bar/."), id);
+    assert_eq!(&*paths.path(id), "/foo/bar");
+    assert_ne!(paths.intern("foo/bar"), id);
+    // `/foo/bar`, `/foo`, `/`, `foo/bar`, `foo` and `.`
+    assert_eq!(paths.len(), 6);
+
+    assert_eq!(paths.get("/foo/"), Some(paths.intern("/foo")));
+    assert_eq!(paths.get("/nope"), None);
+    assert_eq!(&*paths.path(paths.intern("")), ".");
+    assert_eq!(&*paths.path(paths.intern("///")), "/");
+}
+
+#[test]
+fn parent() {
+    let paths = PathInterner::new();
+    let mut id = paths.intern("/a/b/c");
+    let mut ancestors = vec![];
+    while let Some(parent) = paths.parent(id) {
+        ancestors.push(paths.path(parent).to_string());
+        id = parent;
+    }
+    assert_eq!(ancestors, ["/a/b", "/a", "/"]);
+
+    let id = paths.intern("../a");
+    let parent = paths.parent(id).unwrap();
+    assert_eq!(&*paths.path(parent), "..");
+    assert_eq!(paths.parent(parent), Some(paths.intern(".")));
+    assert_eq!(paths.parent(paths.intern(".")), None);
+}
+
+#[test]
+fn deep_paths() {
+    // Interning walks the missing ancestors in a loop, so a small stack is enough however deep the path is.
+    let len = thread::Builder::new()
+        .stack_size(64 * 1024)
+        .spawn(|| {
+            let paths = PathInterner::new();
+            paths.intern(&"/a".repeat(5_000));
+            paths.len()
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+    // Every ancestor, and `/`.
+    assert_eq!(len, 5_001);
+}
+
+#[test]
+fn join() {
+    let paths = PathInterner::new();
+    let root = paths.intern("/");
+    let foo = paths.join(root, "foo");
+    assert_eq!(foo, paths.intern("/foo"));
+    assert_eq!(paths.parent(foo), Some(root));
+    assert_eq!(paths.join(foo, "bar/../baz/"), paths.intern("/foo/baz"));
+    assert_eq!(paths.join(foo, ".."), root);
+    assert_eq!(paths.join(foo, ""), foo);
+    assert_eq!(paths.join(paths.intern("."), "a"), paths.intern("a"));
+    assert_eq!(paths.join(paths.intern("a"), "../.."), paths.intern(".."));
+}
+
+#[test]
+fn relative() {
+    let paths = PathInterner::new();
+    let cases = [
+        ("/var/lib", "/var", ".."),
+        ("/var/lib", "/bin", "../../bin"),
+        ("/var/lib", "/var/lib", ""),
+        ("/var/lib", "/var/apache", "../apache"),
+        ("/var/", "/var/lib", "lib"),
+        ("/", "/var/lib", "var/lib"),
+        ("/foo/bar/baz-quux", "/foo/bar/baz", "../baz"),
+        ("/baz", "/baz-quux", "../baz-quux"),
+        ("/page1/page2/foo", "/", "../../.."),
+        ("a/b", "a/c", "../c"),
+    ];
+    for (from, to, right) in cases {
+        assert_eq!(
+            paths.relative(paths.intern(from), paths.intern(to)),
+            right,
+            "for input from: {} to: {}",
+            from,
+            to
+        );
+    }
+}
+
+#[test]
+fn shared_between_threads() {
+    let paths = Arc::new(PathInterner::new());
+    let handles = (0..8)
+        .map(|i| {
+            let paths = paths.clone();
+            thread::spawn(move || {
+                (0..200)
+                    .map(|j| paths.intern(&format!("/root/{}/{}/file.js", (i + j) % 10, j % 7)))
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        for id in handle.join().unwrap() {
+            let path = paths.path(id);
+            assert_eq!(paths.get(&path), Some(id));
+            assert_eq!(
+                paths
+                    .parent(id)
+                    .map(|parent| paths.path(parent).to_string()),
+                Some(posix::dirname(&path))
+            );
+        }
+    }
+    // `/`, `/root`, 10 directories, 70 subdirectories and 70 files
+    assert_eq!(paths.len(), 2 + 10 + 70 + 70);
+}
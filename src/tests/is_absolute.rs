@@ -1,13 +1,12 @@
 use crate as nodejs_path;
 
-
 #[cfg(target_family = "unix")]
 #[test]
 fn unix() {
-    assert_eq!(nodejs_path::posix::is_absolute("/home/foo"), true);
-    assert_eq!(nodejs_path::posix::is_absolute("/home/foo/.."), true);
-    assert_eq!(nodejs_path::posix::is_absolute("bar/"), false);
-    assert_eq!(nodejs_path::posix::is_absolute("./baz"), false);
+    assert!(nodejs_path::posix::is_absolute("/home/foo"));
+    assert!(nodejs_path::posix::is_absolute("/home/foo/.."));
+    assert!(!nodejs_path::posix::is_absolute("bar/"));
+    assert!(!nodejs_path::posix::is_absolute("./baz"));
 }
 
 #[cfg(target_family = "windows")]
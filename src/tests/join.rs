@@ -53,7 +53,7 @@ fn universal() {
     ];
     join_tests.iter().for_each(|(input, right)| {
         assert_eq!(
-            &nodejs_path::posix::join_impl(&input),
+            &nodejs_path::posix::join_impl(input),
             right,
             "for input {:?}",
             input
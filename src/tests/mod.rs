@@ -1,9 +1,36 @@
 mod basename;
+#[cfg(feature = "resolver")]
+mod browser;
+#[cfg(feature = "resolver")]
+mod builtins;
+mod components;
+mod conformance;
+mod convert;
+#[cfg(feature = "resolver")]
+mod declarations;
 mod dirname;
+#[cfg(feature = "resolver")]
+mod entry;
+#[cfg(feature = "resolver")]
+mod esm;
+#[cfg(feature = "resolver")]
+mod extension_alias;
 mod extname;
+#[cfg(feature = "std")]
+mod find_up;
+#[cfg(feature = "resolver")]
+mod format;
+#[cfg(feature = "std")]
+mod interner;
 mod is_absolute;
 mod join;
 mod normalize;
+#[cfg(feature = "resolver")]
+mod package_scope;
 mod parse_format;
+mod prefix;
+mod properties;
 mod relative;
-mod resolve;
\ No newline at end of file
+mod resolve;
+#[cfg(feature = "resolver")]
+mod specifier;
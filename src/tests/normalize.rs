@@ -1,4 +1,3 @@
-
 use crate as nodejs_path;
 
 #[cfg(target_family = "unix")]
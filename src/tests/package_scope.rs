@@ -0,0 +1,131 @@
+use crate as nodejs_path;
+
+use std::{cell::Cell, io, sync::Arc};
+
+use nodejs_path::resolver::{FileKind, FileSystem, MemoryFileSystem, PackageScopes};
+
+// Counts `kind` queries, to check which directories get looked at.
+struct Counting {
+    fs: MemoryFileSystem,
+    queries: Cell<usize>,
+}
+
+impl FileSystem for Counting {
+    fn kind(&self, path: &str) -> Option<FileKind> {
+        self.queries.set(self.queries.get() + 1);
+        self.fs.kind(path)
+    }
+
+    fn read_to_string(&self, path: &str) -> io::Result<String> {
+        self.fs.read_to_string(path)
+    }
+
+    fn canonicalize(&self, path: &str) -> io::Result<String> {
+        self.fs.canonicalize(path)
+    }
+}
+
+fn project() -> MemoryFileSystem {
+    let mut fs = MemoryFileSystem::new();
+    fs.add_file(
+        "/app/package.json",
+        r#"{ "name": "app", "type": "module" }"#,
+    )
+    .add_file("/app/src/a.js", "")
+    .add_file("/app/src/b.js", "")
+    .add_file("/app/src/nested/c.js", "")
+    .add_file(
+        "/app/src/typed/package.json",
+        r#"{ "type": "commonjs", "name": null }"#,
+    )
+    .add_file("/app/src/typed/d.js", "")
+    .add_file("/app/node_modules/dep/package.json", r#"{ "name": "dep" }"#)
+    .add_file("/app/node_modules/dep/lib/e.js", "")
+    .add_file("/app/node_modules/bare/f.js", "")
+    .add_file("/app/node_modules/stray.js", "")
+    .add_file("/app/node_modules/package.json", "{}")
+    .add_file("/loose/g.js", "")
+    .add_file("/broken/package.json", "{")
+    .add_file("/broken/h.js", "");
+    fs
+}
+
+#[test]
+fn scopes() {
+    let fs = project();
+    let scopes = PackageScopes::new(&fs);
+    let cases = [
+        ("/app/src/a.js", Some("/app")),
+        ("/app/src/nested/c.js", Some("/app")),
+        ("/app/src/../package.json", Some("/app")),
+        ("/app/src/typed/d.js", Some("/app/src/typed")),
+        (
+            "/app/node_modules/dep/lib/e.js",
+            Some("/app/node_modules/dep"),
+        ),
+        ("/app/node_modules/bare/f.js", None),
+        ("/app/node_modules/stray.js", None),
+        ("/loose/g.js", None),
+        ("/", None),
+    ];
+    for (path, expected) in cases {
+        let scope = scopes.find_package_scope(path).unwrap();
+        assert_eq!(
+            scope.as_ref().map(|scope| scope.dir()),
+            expected,
+            "{}",
+            path
+        );
+    }
+
+    let app = scopes.find_package_scope("/app/src/a.js").unwrap().unwrap();
+    assert_eq!(app.name(), Some("app"));
+    assert_eq!(app.package_type(), Some("module"));
+    assert_eq!(app.package_json_path(), "/app/package.json");
+    assert_eq!(app.field("name"), Some(&serde_json::json!("app")));
+    assert_eq!(app.field("exports"), None);
+
+    let typed = scopes
+        .find_package_scope("/app/src/typed/d.js")
+        .unwrap()
+        .unwrap();
+    assert_eq!(typed.name(), None);
+    assert_eq!(typed.package_type(), Some("commonjs"));
+
+    let err = scopes.find_package_scope("/broken/h.js").unwrap_err();
+    assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+}
+
+#[test]
+fn cache() {
+    let fs = Counting {
+        fs: project(),
+        queries: Cell::new(0),
+    };
+    let scopes = PackageScopes::new(&fs);
+
+    let a = scopes.find_package_scope("/app/src/a.js").unwrap().unwrap();
+    assert_eq!(fs.queries.get(), 2);
+
+    let b = scopes.find_package_scope("/app/src/b.js").unwrap().unwrap();
+    let nested = scopes
+        .find_package_scope("/app/src/nested/c.js")
+        .unwrap()
+        .unwrap();
+    assert!(Arc::ptr_eq(&a, &b));
+    assert!(Arc::ptr_eq(&a, &nested));
+    assert_eq!(fs.queries.get(), 3);
+
+    scopes.find_package_scope("/loose/g.js").unwrap();
+    scopes.find_package_scope("/loose/other.js").unwrap();
+    assert_eq!(fs.queries.get(), 5);
+
+    scopes.find_package_scope("/broken/h.js").unwrap_err();
+    scopes.find_package_scope("/broken/h.js").unwrap_err();
+    assert_eq!(fs.queries.get(), 7);
+
+    scopes.clear();
+    let again = scopes.find_package_scope("/app/src/a.js").unwrap().unwrap();
+    assert!(!Arc::ptr_eq(&a, &again));
+    assert_eq!(fs.queries.get(), 9);
+}
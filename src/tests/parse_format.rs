@@ -1,6 +1,5 @@
 use crate as nodejs_path;
 
-
 use nodejs_path::{
     posix::{self as posix},
     Parsed,
@@ -14,7 +13,7 @@ fn check_parse_format_for_posix(paths: &[(&str, &str)]) {
         assert!(output.dir.starts_with(&output.root));
         assert_eq!(
             output.dir,
-            if output.dir.len() != 0 {
+            if !output.dir.is_empty() {
                 posix::dirname(element)
             } else {
                 "".to_owned()
@@ -249,7 +248,7 @@ fn posix_tests() {
     ];
 
     trailing_tests_posix.iter().for_each(|(input, right)| {
-        let left = nodejs_path::posix::parse(&input);
+        let left = nodejs_path::posix::parse(input);
         assert_eq!(&left, right);
     })
 
@@ -0,0 +1,93 @@
+use crate as nodejs_path;
+
+use nodejs_path::win32::{self, Prefix};
+
+#[test]
+fn classify() {
+    let cases = [
+        ("C:\\foo", Some(Prefix::Drive('C'))),
+        ("c:/", Some(Prefix::Drive('c'))),
+        ("C:", Some(Prefix::DriveRelative('C'))),
+        ("C:foo\\bar", Some(Prefix::DriveRelative('C'))),
+        (
+            "\\\\server\\share",
+            Some(Prefix::Unc {
+                server: "server",
+                share: "share",
+            }),
+        ),
+        (
+            "//server//share/foo",
+            Some(Prefix::Unc {
+                server: "server",
+                share: "share",
+            }),
+        ),
+        ("\\\\?\\C:\\foo", Some(Prefix::Verbatim("C:"))),
+        (
+            "\\\\?\\GLOBALROOT\\Device\\HarddiskVolume1\\foo",
+            Some(Prefix::Verbatim("GLOBALROOT")),
+        ),
+        (
+            "\\\\?\\UNC\\srv\\share\\foo",
+            Some(Prefix::VerbatimUnc {
+                server: "srv",
+                share: "share",
+            }),
+        ),
+        (
+            "\\\\?\\unc\\srv",
+            Some(Prefix::VerbatimUnc {
+                server: "srv",
+                share: "",
+            }),
+        ),
+        ("\\\\.\\pipe\\name", Some(Prefix::DeviceNamespace("pipe"))),
+        ("\\\\.\\COM1", Some(Prefix::DeviceNamespace("COM1"))),
+        ("\\foo", Some(Prefix::RootedWithoutDrive)),
+        ("/", Some(Prefix::RootedWithoutDrive)),
+        ("\\\\", Some(Prefix::RootedWithoutDrive)),
+        ("\\\\server", Some(Prefix::RootedWithoutDrive)),
+        ("\\\\server\\", Some(Prefix::RootedWithoutDrive)),
+        ("\\\\?\\", Some(Prefix::RootedWithoutDrive)),
+        ("\\\\\\server\\share", Some(Prefix::RootedWithoutDrive)),
+        ("", None),
+        ("c", None),
+        ("foo\\bar", None),
+        ("1:\\foo", None),
+        ("é:\\foo", None),
+    ];
+
+    for (path, expected) in cases {
+        assert_eq!(win32::prefix(path), expected, "prefix({:?})", path);
+        assert_eq!(
+            win32::is_absolute(path),
+            expected.is_some_and(|prefix| prefix.is_absolute()),
+            "is_absolute({:?})",
+            path
+        );
+    }
+}
+
+// Roots as returned by node's `path.win32.parse()`.
+#[test]
+fn root_len() {
+    let cases = [
+        ("\\\\?\\C:\\foo", "\\\\?\\C:\\"),
+        ("\\\\.\\pipe\\name", "\\\\.\\pipe\\"),
+        ("\\\\?\\UNC\\srv\\share\\x", "\\\\?\\UNC\\"),
+        ("\\\\?\\GLOBALROOT\\Device\\x", "\\\\?\\GLOBALROOT\\"),
+        ("\\\\server\\share", "\\\\server\\share"),
+        ("\\\\server\\share\\", "\\\\server\\share\\"),
+        ("\\\\server", "\\"),
+        ("\\\\?\\", "\\"),
+        ("//?/c:/x", "//?/c:/"),
+        ("C:\\foo", "C:\\"),
+        ("C:foo", "C:"),
+        ("foo", ""),
+    ];
+
+    for (path, root) in cases {
+        assert_eq!(&path[..win32::root_len(path)], root, "root of {:?}", path);
+    }
+}
@@ -0,0 +1,199 @@
+use crate as nodejs_path;
+
+use nodejs_path::{posix, win32};
+use proptest::prelude::*;
+
+fn segment() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just(".".to_owned()),
+        Just("..".to_owned()),
+        Just("...".to_owned()),
+        Just("".to_owned()),
+        "[a-z]{1,3}",
+        "[a-z.]{1,4}",
+        "\\.[a-z]{1,3}",
+        "[a-z]{1,3}\\.[a-z]{1,3}",
+        "[éü🦀 ]{1,2}",
+    ]
+}
+
+/// Segments joined by runs of `/`, with optional leading and trailing runs.
+fn posix_path() -> impl Strategy<Value = String> {
+    (
+        "/{0,3}",
+        prop::collection::vec((segment(), "/{1,3}"), 0..6),
+        segment(),
+    )
+        .prop_map(|(lead, segments, last)| {
+            let mut path = lead;
+            for (segment, separator) in segments {
+                path.push_str(&segment);
+                path.push_str(&separator);
+            }
+            path.push_str(&last);
+            path
+        })
+}
+
+fn absolute_posix_path() -> impl Strategy<Value = String> {
+    posix_path().prop_map(|path| format!("/{}", path))
+}
+
+fn win32_separator() -> impl Strategy<Value = String> {
+    "[/\\\\]{1,2}"
+}
+
+fn win32_root() -> impl Strategy<Value = String> {
+    prop_oneof![
+        Just("".to_owned()),
+        // Drive letters, absolute and drive-relative
+        "[a-zA-Z]:",
+        "[a-zA-Z]:[/\\\\]",
+        // UNC
+        "[/\\\\]{2}[a-z]{1,3}[/\\\\][a-z$]{1,3}[/\\\\]",
+        // Namespaced
+        "[/\\\\]{2}[?.][/\\\\]([A-Z]:|UNC[/\\\\][a-z]{1,3}[/\\\\][a-z]{1,3})[/\\\\]",
+        "[/\\\\]",
+    ]
+}
+
+fn win32_path() -> impl Strategy<Value = String> {
+    (
+        win32_root(),
+        prop::collection::vec((segment(), win32_separator()), 0..4),
+        segment(),
+    )
+        .prop_map(|(root, segments, last)| {
+            let mut path = root;
+            for (segment, separator) in segments {
+                path.push_str(&segment);
+                path.push_str(&separator);
+            }
+            path.push_str(&last);
+            path
+        })
+}
+
+proptest! {
+    #[test]
+    fn posix_normalize_is_idempotent(path in posix_path()) {
+        let normalized = posix::normalize(&path);
+        prop_assert_eq!(posix::normalize(&normalized), normalized);
+    }
+
+    #[test]
+    fn posix_format_parse_round_trips(path in posix_path()) {
+        // `parse` collapses a leading run of separators into the root, so node doesn't round trip those.
+        if !path.starts_with("//") {
+            let parsed = posix::parse(&path);
+            prop_assert_eq!(&posix::parse(&posix::format(parsed.clone())), &parsed);
+            // `parse` also drops trailing separators.
+            if !path.ends_with('/') {
+                prop_assert_eq!(posix::format(parsed), path);
+            }
+        }
+    }
+
+    #[test]
+    fn posix_resolve_relative_round_trips(from in posix_path(), to in posix_path()) {
+        let relative = posix::relative_with_cwd(&from, &to, "/cwd");
+        prop_assert_eq!(
+            posix::resolve_impl_with_cwd(&[&from, &relative], "/cwd"),
+            posix::resolve_impl_with_cwd(&[&to], "/cwd")
+        );
+    }
+
+    #[test]
+    fn posix_base_dir_matches_resolve_and_relative(base in posix_path(), path in posix_path()) {
+        let dir = posix::BaseDir::with_cwd(&base, "/cwd");
+        prop_assert_eq!(dir.resolve(&path), posix::resolve_impl_with_cwd(&[&base, &path], "/cwd"));
+        prop_assert_eq!(dir.relative(&path), posix::relative_with_cwd(&base, &path, "/cwd"));
+    }
+
+    #[cfg(feature = "std")]
+    #[test]
+    fn interner_matches_dirname_and_relative(from in absolute_posix_path(), to in absolute_posix_path()) {
+        let paths = nodejs_path::PathInterner::new();
+        let (from_id, to_id) = (paths.intern(&from), paths.intern(&to));
+        prop_assert_eq!(paths.relative(from_id, to_id), posix::relative(&from, &to));
+        let normalized = paths.path(from_id);
+        prop_assert_eq!(
+            paths.parent(from_id).map(|parent| paths.path(parent).to_string()),
+            Some(posix::dirname(&normalized)).filter(|dirname| *dirname != *normalized)
+        );
+    }
+
+    #[test]
+    fn posix_resolve_is_absolute(paths in prop::collection::vec(posix_path(), 0..4)) {
+        let resolved = posix::resolve_impl_with_cwd(&paths, "/cwd");
+        prop_assert!(posix::is_absolute(&resolved));
+        prop_assert_eq!(posix::normalize(&resolved), resolved);
+    }
+
+    // Empty segments are skipped by `join`, but a join that produces `.` is not, so they are left out here. An
+    // absolute middle segment can't climb above its own root once joined on its own, so it is left out as well.
+    #[test]
+    fn posix_join_is_associative(
+        a in posix_path().prop_filter("non-empty", |p| !p.is_empty()),
+        b in posix_path().prop_filter("relative", |p| !p.is_empty() && !p.starts_with('/')),
+        c in prop_oneof![posix_path(), absolute_posix_path()].prop_filter("non-empty", |p| !p.is_empty()),
+    ) {
+        let left = posix::join_impl(&[posix::join_impl(&[&a, &b]), c.clone()]);
+        let right = posix::join_impl(&[a.clone(), posix::join_impl(&[&b, &c])]);
+        prop_assert_eq!(posix::normalize(&left), posix::normalize(&right));
+        prop_assert_eq!(posix::normalize(&left), posix::join_impl(&[&a, &b, &c]));
+    }
+
+    #[test]
+    fn components_are_double_ended(path in prop_oneof![posix_path(), win32_path()], lexical in any::<bool>()) {
+        for components in [posix::components(&path), win32::components(&path)] {
+            let components = if lexical { components.lexical() } else { components };
+            let mut reversed = components.clone().rev().collect::<Vec<_>>();
+            reversed.reverse();
+            prop_assert_eq!(components.collect::<Vec<_>>(), reversed);
+        }
+    }
+
+    #[test]
+    fn to_posix_round_trips(path in win32_path()) {
+        use nodejs_path::{to_posix, to_win32, DriveMapping};
+        for drives in [DriveMapping::Msys, DriveMapping::Wsl] {
+            if let Some(posix) = to_posix(&path, drives) {
+                let win32 = to_win32(&posix, drives);
+                prop_assert!(win32.is_some(), "to_win32({:?})", posix);
+                prop_assert_eq!(to_posix(&win32.unwrap(), drives), Some(posix));
+            }
+        }
+    }
+
+    #[test]
+    fn win32_is_absolute_ignores_separator_style(path in win32_path()) {
+        prop_assert_eq!(win32::is_absolute(&path), win32::is_absolute(&path.replace('/', "\\")));
+        prop_assert_eq!(win32::is_absolute(&path), win32::is_absolute(&path.replace('\\', "/")));
+    }
+
+    #[test]
+    fn win32_is_absolute_matches_root(path in win32_path()) {
+        let chars = path.chars().collect::<Vec<_>>();
+        let is_separator = |c: Option<&char>| matches!(c, Some('/') | Some('\\'));
+        let expected = is_separator(chars.first())
+            || (chars.first().is_some_and(char::is_ascii_alphabetic)
+                && chars.get(1) == Some(&':')
+                && is_separator(chars.get(2)));
+        prop_assert_eq!(win32::is_absolute(&path), expected);
+    }
+}
+
+// Counterexamples found by the properties above. They match node, so they pin the behavior instead.
+#[test]
+fn regressions() {
+    // `parse` is not a fixed point for a doubled root followed by `..`.
+    let parsed = posix::parse("//..");
+    assert_eq!(parsed.ext, "");
+    assert_eq!(posix::format(parsed), "/..");
+    assert_eq!(posix::parse("/..").ext, ".");
+
+    // An absolute middle segment climbs above `a` only when joined together with it.
+    assert_eq!(posix::join!("a", "/../.", "/."), ".");
+    assert_eq!(posix::join!("a", &posix::join!("/../.", "/.")), "a/");
+}
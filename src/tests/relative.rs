@@ -1,6 +1,6 @@
 use crate as nodejs_path;
 
-#[cfg(target_family = "unix")]
+#[cfg(all(target_family = "unix", feature = "std"))]
 #[test]
 fn unix() {
     let cases = [
@@ -33,3 +33,123 @@ fn unix() {
         );
     })
 }
+
+#[test]
+fn with_cwd() {
+    let cases = [
+        ("a/b", "a/c", "../c"),
+        ("a", "/tmp", ".."),
+        ("/tmp/a", "b", "../b"),
+        ("/var", "lib", "../tmp/lib"),
+        (".", "/tmp", ""),
+    ];
+
+    cases.into_iter().for_each(|(from, to, right)| {
+        assert_eq!(
+            nodejs_path::posix::relative_with_cwd(from, to, "/tmp"),
+            right,
+            "for input from: {} to: {}",
+            from,
+            to
+        );
+    })
+}
+
+#[test]
+fn base_dir() {
+    let cases = [
+        ("/var/lib", "/var", ".."),
+        ("/var/lib", "/bin", "../../bin"),
+        ("/var/lib", "/var/lib", ""),
+        ("/var/lib", "apache", "../../tmp/apache"),
+        ("/", "/var/lib", "var/lib"),
+        ("/foo/bar/baz", "../baz-quux", "../../../baz-quux"),
+        ("/page1/page2/foo", "/", "../../.."),
+        ("a", "/tmp", ".."),
+        ("é/🦀", "/tmp/é/🦀s", "../🦀s"),
+    ];
+
+    let mut paths = vec![];
+    let mut expected = vec![];
+    cases.into_iter().for_each(|(from, to, right)| {
+        let base = nodejs_path::posix::BaseDir::with_cwd(from, "/tmp");
+        assert_eq!(
+            base.relative(to),
+            right,
+            "for input from: {} to: {}",
+            from,
+            to
+        );
+        paths.push(nodejs_path::posix::resolve_impl_with_cwd(&[to], "/tmp"));
+        expected.push(nodejs_path::posix::relative_with_cwd(
+            "/var/lib", to, "/tmp",
+        ));
+    });
+
+    let mut actual = vec![];
+    nodejs_path::posix::BaseDir::with_cwd("/var/lib", "/tmp")
+        .relative_many(&paths, |relative| actual.push(relative.to_owned()));
+    assert_eq!(actual, expected);
+}
+
+#[cfg(all(target_family = "unix", feature = "std"))]
+#[test]
+fn physical() {
+    use std::{fs, os::unix::fs::symlink};
+
+    // A pnpm-style layout: `node_modules/foo` links into the store, where `foo` sees `bar` as a sibling link.
+    let root = std::env::temp_dir().join(format!(
+        "nodejs_path_relative_physical_{}",
+        std::process::id()
+    ));
+    let _ = fs::remove_dir_all(&root);
+    let store = root.join("node_modules/.pnpm");
+    fs::create_dir_all(store.join("foo@1/node_modules/foo")).unwrap();
+    fs::create_dir_all(store.join("bar@1/node_modules/bar")).unwrap();
+    symlink(
+        "../../bar@1/node_modules/bar",
+        store.join("foo@1/node_modules/bar"),
+    )
+    .unwrap();
+    symlink(
+        ".pnpm/foo@1/node_modules/foo",
+        root.join("node_modules/foo"),
+    )
+    .unwrap();
+
+    let root = fs::canonicalize(&root).unwrap();
+    let path = |p: &str| root.join(p).to_str().unwrap().to_owned();
+
+    // No symlinks in between
+    let relative =
+        nodejs_path::posix::relative_physical(&path(""), &path("node_modules/.pnpm")).unwrap();
+    assert_eq!(relative.path, "node_modules/.pnpm");
+    assert!(relative.lexically_equivalent);
+
+    // `to` is a symlink, which the lexical path follows just as well
+    let relative =
+        nodejs_path::posix::relative_physical(&path(""), &path("node_modules/foo")).unwrap();
+    assert_eq!(relative.path, "node_modules/foo");
+    assert!(relative.lexically_equivalent);
+
+    // `from` is reached through a symlink, so `..` climbs out of the store instead of `node_modules`
+    let relative = nodejs_path::posix::relative_physical(
+        &path("node_modules/foo"),
+        &path("node_modules/.pnpm/foo@1/node_modules/bar"),
+    )
+    .unwrap();
+    assert_eq!(relative.path, "../../../bar@1/node_modules/bar");
+    assert!(!relative.lexically_equivalent);
+    assert_eq!(
+        nodejs_path::posix::relative(
+            &path("node_modules/foo"),
+            &path("node_modules/.pnpm/foo@1/node_modules/bar")
+        ),
+        "../.pnpm/foo@1/node_modules/bar"
+    );
+
+    let missing = nodejs_path::posix::relative_physical(&path(""), &path("missing")).unwrap_err();
+    assert_eq!(missing.kind(), std::io::ErrorKind::NotFound);
+
+    fs::remove_dir_all(&root).unwrap();
+}
@@ -2,6 +2,7 @@ use crate as nodejs_path;
 
 use nodejs_path::posix;
 
+#[cfg(feature = "std")]
 fn posixy_cwd() -> String {
     let cwd = std::env::current_dir()
         .unwrap()
@@ -17,10 +18,10 @@ fn posixy_cwd() -> String {
             .collect();
     }
 
-    return cwd;
+    cwd
 }
 
-#[cfg(target_family = "unix")]
+#[cfg(all(target_family = "unix", feature = "std"))]
 #[test]
 fn posix_test() {
     // nodejs_path::resolve_with_array(&[]);
@@ -37,7 +38,7 @@ fn posix_test() {
     ];
 
     tests.iter().for_each(|(input, right)| {
-        assert_eq!(posix::resolve_impl(&input), *right);
+        assert_eq!(posix::resolve_impl(input), *right);
     });
 
     assert_eq!(posix::resolve!("/var/lib", "../", "file/"), "/var/file");
@@ -50,3 +51,50 @@ fn posix_test() {
         "/foo/tmp.3/cycles/root.js"
     );
 }
+
+#[test]
+fn posix_with_cwd_test() {
+    let tests = [
+        (vec!["a/b/c/", "../../.."], "/tmp"),
+        (vec!["."], "/tmp"),
+        (vec![], "/tmp"),
+        (vec!["a", "./b"], "/tmp/a/b"),
+        (vec!["/some/dir", ".", "/absolute/"], "/absolute"),
+    ];
+
+    tests.iter().for_each(|(input, right)| {
+        assert_eq!(posix::resolve_impl_with_cwd(input, "/tmp"), *right);
+    });
+
+    assert_eq!(posix::resolve_impl_with_cwd(&["a"], ""), "a");
+    assert_eq!(posix::resolve_impl_with_cwd::<&str>(&[], ""), ".");
+}
+
+#[test]
+fn base_dir_test() {
+    let tests = [
+        ("/var/lib", "../file/", "/var/file"),
+        ("/var/lib", "/../file/", "/file"),
+        ("a/b/c/", "../../..", "/tmp"),
+        (".", "", "/tmp"),
+        ("/some/dir", "/absolute/", "/absolute"),
+        (
+            "/foo/tmp.3/",
+            "../tmp.3/cycles/root.js",
+            "/foo/tmp.3/cycles/root.js",
+        ),
+        ("/", "..//a//b/", "/a/b"),
+        ("/é", "./🦀/../x", "/é/x"),
+    ];
+
+    tests.iter().for_each(|(base, path, right)| {
+        let mut base = posix::BaseDir::with_cwd(base, "/tmp");
+        assert_eq!(base.resolve(path), *right);
+        base.resolve_many([path, path], |resolved| assert_eq!(resolved, *right));
+    });
+
+    // Without an absolute cwd, the base stays relative.
+    let base = posix::BaseDir::with_cwd("a", "");
+    assert_eq!(base.as_str(), "a");
+    assert_eq!(base.resolve("../../b"), "../b");
+}
@@ -0,0 +1,214 @@
+use crate as nodejs_path;
+
+use nodejs_path::resolver::{
+    parse_specifier, validate_package_name, PackageNameError, Specifier, SpecifierError,
+};
+
+#[test]
+fn kinds() {
+    let cases = [
+        (".", Specifier::Relative(".")),
+        ("..", Specifier::Relative("..")),
+        ("./a.js", Specifier::Relative("./a.js")),
+        ("../a", Specifier::Relative("../a")),
+        (".\\a", Specifier::Relative(".\\a")),
+        ("..\\a", Specifier::Relative("..\\a")),
+        ("/a/b", Specifier::Absolute("/a/b")),
+        ("C:\\a", Specifier::Absolute("C:\\a")),
+        ("c:/a", Specifier::Absolute("c:/a")),
+        (
+            "\\\\server\\share",
+            Specifier::Absolute("\\\\server\\share"),
+        ),
+        ("fs", Specifier::Builtin("fs")),
+        ("fs/promises", Specifier::Builtin("fs/promises")),
+        ("node:fs", Specifier::Builtin("fs")),
+        ("node:test", Specifier::Builtin("test")),
+        ("#internal", Specifier::PackageImport("#internal")),
+        ("#internal/a.js", Specifier::PackageImport("#internal/a.js")),
+        ("file:///a.js", Specifier::Url("file:///a.js")),
+        (
+            "data:text/javascript,1",
+            Specifier::Url("data:text/javascript,1"),
+        ),
+        ("https://esm.sh/x", Specifier::Url("https://esm.sh/x")),
+        (
+            "pkg",
+            Specifier::Bare {
+                name: "pkg",
+                subpath: "",
+            },
+        ),
+        (
+            "pkg/",
+            Specifier::Bare {
+                name: "pkg",
+                subpath: "/",
+            },
+        ),
+        (
+            "pkg/a/b.js",
+            Specifier::Bare {
+                name: "pkg",
+                subpath: "/a/b.js",
+            },
+        ),
+        (
+            "@scope/pkg",
+            Specifier::Bare {
+                name: "@scope/pkg",
+                subpath: "",
+            },
+        ),
+        (
+            "@scope/pkg/sub/path.js",
+            Specifier::Bare {
+                name: "@scope/pkg",
+                subpath: "/sub/path.js",
+            },
+        ),
+        // Only prefixed, so a package otherwise.
+        (
+            "test",
+            Specifier::Bare {
+                name: "test",
+                subpath: "",
+            },
+        ),
+        (
+            "fs-extra",
+            Specifier::Bare {
+                name: "fs-extra",
+                subpath: "",
+            },
+        ),
+    ];
+    for (specifier, expected) in cases {
+        assert_eq!(
+            parse_specifier(specifier).unwrap(),
+            expected,
+            "{:?}",
+            specifier
+        );
+    }
+}
+
+#[test]
+fn errors() {
+    let invalid_name = |name: &str, reason| SpecifierError::InvalidPackageName {
+        name: name.to_owned(),
+        reason,
+    };
+    let cases = [
+        ("", SpecifierError::Empty),
+        ("#", SpecifierError::InvalidPackageImport("#".to_owned())),
+        (
+            "#/a",
+            SpecifierError::InvalidPackageImport("#/a".to_owned()),
+        ),
+        (
+            "node:nope",
+            SpecifierError::UnknownBuiltinModule("node:nope".to_owned()),
+        ),
+        (
+            "#foo/",
+            SpecifierError::InvalidPackageImport("#foo/".to_owned()),
+        ),
+        (
+            "#foo/bar/",
+            SpecifierError::InvalidPackageImport("#foo/bar/".to_owned()),
+        ),
+        (
+            "NODE:fs",
+            SpecifierError::UnknownBuiltinModule("NODE:fs".to_owned()),
+        ),
+        (
+            "Node:nope",
+            SpecifierError::UnknownBuiltinModule("Node:nope".to_owned()),
+        ),
+        (
+            "@scope",
+            invalid_name("@scope", PackageNameError::MissingScopedName),
+        ),
+        (
+            "@scope/",
+            invalid_name("@scope/", PackageNameError::MissingScopedName),
+        ),
+        (
+            ".bin/x",
+            invalid_name(".bin", PackageNameError::LeadingPeriod),
+        ),
+        (
+            "_x",
+            invalid_name("_x", PackageNameError::LeadingUnderscore),
+        ),
+        (
+            " x",
+            invalid_name(" x", PackageNameError::LeadingOrTrailingSpaces),
+        ),
+        (
+            "node_modules/x",
+            invalid_name("node_modules", PackageNameError::Blocklisted),
+        ),
+        (
+            "a%20b",
+            invalid_name("a%20b", PackageNameError::NotUrlFriendly),
+        ),
+        (
+            "a\\b",
+            invalid_name("a\\b", PackageNameError::NotUrlFriendly),
+        ),
+    ];
+    for (specifier, expected) in cases {
+        assert_eq!(
+            parse_specifier(specifier).unwrap_err(),
+            expected,
+            "{:?}",
+            specifier
+        );
+    }
+    assert_eq!(
+        parse_specifier("@scope").unwrap_err().to_string(),
+        "invalid package name \"@scope\": scoped name must have a name after the scope"
+    );
+}
+
+#[test]
+fn package_names() {
+    for name in [
+        "a",
+        "some-package",
+        "example.com",
+        "under_score",
+        "period.js",
+        "123numeric",
+        "crazy!",
+        "JSONStream",
+        "@npm/thingy",
+        "@jane/foo.js",
+    ] {
+        assert_eq!(validate_package_name(name), Ok(()), "{:?}", name);
+    }
+
+    let cases = [
+        ("", PackageNameError::Empty),
+        (".start-with-period", PackageNameError::LeadingPeriod),
+        ("@scope/.name", PackageNameError::LeadingPeriod),
+        (
+            "_start-with-underscore",
+            PackageNameError::LeadingUnderscore,
+        ),
+        ("contain:colons", PackageNameError::NotUrlFriendly),
+        (" leading-space", PackageNameError::LeadingOrTrailingSpaces),
+        ("trailing-space ", PackageNameError::LeadingOrTrailingSpaces),
+        ("s/l/a/s/h/e/s", PackageNameError::NotUrlFriendly),
+        ("node_modules", PackageNameError::Blocklisted),
+        ("favicon.ico", PackageNameError::Blocklisted),
+        ("@/name", PackageNameError::NotUrlFriendly),
+        ("@scope/a/b", PackageNameError::NotUrlFriendly),
+        ("ünicode", PackageNameError::NotUrlFriendly),
+    ];
+    for (name, expected) in cases {
+        assert_eq!(validate_package_name(name), Err(expected), "{:?}", name);
+    }
+}
@@ -0,0 +1,300 @@
+//! Differential tests against a locally installed Node.js.
+//!
+//! Random and edge-case paths are run through both `posix::*` and `node -e` in batches, and every divergence is
+//! reported together with a minimized input. The test is opt-in and skips itself when `node` is not installed:
+//!
+//! ```sh
+//! cargo test --test differential -- --ignored
+//! NODEJS_PATH_DIFFERENTIAL_SEED=42 NODEJS_PATH_DIFFERENTIAL_CASES=100000 cargo test --test differential -- --ignored
+//! ```
+
+use std::{
+    io::Write,
+    process::{Command, Stdio},
+};
+
+use nodejs_path::{posix, Parsed};
+use serde_json::{json, Value};
+
+/// Reads a JSON array of `[method, ...args]` calls from stdin and prints the JSON array of results.
+const NODE_SCRIPT: &str = r#"
+const path = require('path').posix;
+let input = '';
+process.stdin.setEncoding('utf8');
+process.stdin.on('data', (chunk) => (input += chunk));
+process.stdin.on('end', () => {
+  const results = JSON.parse(input).map(([method, ...args]) => {
+    if (method === 'formatParse') return path.format(path.parse(args[0]));
+    return path[method](...args);
+  });
+  process.stdout.write(JSON.stringify(results));
+});
+"#;
+
+#[derive(Debug, Clone, PartialEq)]
+struct Call {
+    method: &'static str,
+    args: Vec<String>,
+}
+
+impl Call {
+    fn new(method: &'static str, args: &[&str]) -> Self {
+        Self {
+            method,
+            args: args.iter().map(|arg| arg.to_string()).collect(),
+        }
+    }
+
+    fn to_json(&self) -> Value {
+        let mut call = vec![json!(self.method)];
+        call.extend(self.args.iter().map(|arg| json!(arg)));
+        Value::Array(call)
+    }
+
+    fn run(&self) -> Value {
+        let args = self.args.iter().map(String::as_str).collect::<Vec<_>>();
+        match (self.method, args.as_slice()) {
+            ("normalize", [path]) => json!(posix::normalize(path)),
+            ("dirname", [path]) => json!(posix::dirname(path)),
+            ("basename", [path]) => json!(posix::basename_impl(path)),
+            ("basename", [path, ext]) => json!(posix::basename_impl_without_ext(path, ext)),
+            ("extname", [path]) => json!(posix::extname(path)),
+            ("isAbsolute", [path]) => json!(posix::is_absolute(path)),
+            ("parse", [path]) => parsed_to_json(posix::parse(path)),
+            ("formatParse", [path]) => json!(posix::format(posix::parse(path))),
+            ("join", paths) => json!(posix::join_impl(paths)),
+            ("resolve", paths) => json!(posix::resolve_impl(paths)),
+            ("relative", [from, to]) => json!(posix::relative(from, to)),
+            (method, args) => unreachable!("{}({:?})", method, args),
+        }
+    }
+
+    fn len(&self) -> usize {
+        self.args.iter().map(|arg| arg.chars().count()).sum()
+    }
+}
+
+fn parsed_to_json(parsed: Parsed) -> Value {
+    json!({
+        "root": parsed.root,
+        "dir": parsed.dir,
+        "base": parsed.base,
+        "ext": parsed.ext,
+        "name": parsed.name,
+    })
+}
+
+fn node_available() -> bool {
+    Command::new("node")
+        .arg("--version")
+        .output()
+        .map(|output| output.status.success())
+        .unwrap_or(false)
+}
+
+fn run_in_node(calls: &[Call]) -> Vec<Value> {
+    let mut child = Command::new("node")
+        .args(["-e", NODE_SCRIPT])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .expect("failed to spawn node");
+    let input = Value::Array(calls.iter().map(Call::to_json).collect()).to_string();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(input.as_bytes())
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success(), "node failed: {:?}", output);
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+fn divergences(calls: &[Call]) -> Vec<(Call, Value, Value)> {
+    let mut found = vec![];
+    // Keep each node invocation reasonably small.
+    for chunk in calls.chunks(5_000) {
+        let expected = run_in_node(chunk);
+        for (call, expected) in chunk.iter().zip(expected) {
+            let actual = call.run();
+            if actual != expected {
+                found.push((call.clone(), actual, expected));
+            }
+        }
+    }
+    found
+}
+
+/// Greedily removes characters and arguments as long as the call keeps diverging.
+fn minimize(call: &Call) -> Call {
+    let mut current = call.clone();
+    loop {
+        let mut candidates = vec![];
+        for (i, arg) in current.args.iter().enumerate() {
+            let chars = arg.chars().collect::<Vec<_>>();
+            for j in 0..chars.len() {
+                let mut candidate = current.clone();
+                candidate.args[i] = chars[..j].iter().chain(&chars[j + 1..]).collect();
+                candidates.push(candidate);
+            }
+            if matches!(current.method, "join" | "resolve") {
+                let mut candidate = current.clone();
+                candidate.args.remove(i);
+                candidates.push(candidate);
+            }
+        }
+        candidates.sort_by_key(Call::len);
+        match divergences(&candidates).into_iter().next() {
+            Some((smaller, _, _)) => current = smaller,
+            None => return current,
+        }
+    }
+}
+
+/// A small xorshift generator, so runs are reproducible from a seed.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn below(&mut self, n: usize) -> usize {
+        (self.next() % n as u64) as usize
+    }
+
+    fn path(&mut self) -> String {
+        const PIECES: &[&str] = &[
+            "/", "/", "//", ".", ".", "..", "a", "b", "foo", ".js", ".d.ts", "\\", ":", " ", "é",
+            "🦀",
+        ];
+        let len = self.below(9);
+        (0..len).map(|_| PIECES[self.below(PIECES.len())]).collect()
+    }
+}
+
+const EDGE_CASES: &[&str] = &[
+    "",
+    ".",
+    "..",
+    "...",
+    "/",
+    "//",
+    "///",
+    "./",
+    "../",
+    "/.",
+    "/..",
+    "/../..",
+    "a/..",
+    "a/../..",
+    "./a",
+    ".a",
+    ".a.",
+    "a.",
+    "a..",
+    "..a",
+    "a.b.c",
+    "/a/b/",
+    "/a/b//",
+    "//a",
+    "///a",
+    "a//b",
+    "a/./b",
+    "a/../b",
+    ".bashrc",
+    ".bashrc.",
+    "/foo/",
+    "C:\\foo",
+    "\\",
+    " ",
+    "é/🦀.rs",
+    "/foo///bar.baz",
+];
+
+fn env_number(name: &str, default: u64) -> u64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+fn calls(rng: &mut Rng, count: u64) -> Vec<Call> {
+    let mut paths = EDGE_CASES.iter().map(|p| p.to_string()).collect::<Vec<_>>();
+    paths.extend((0..count).map(|_| rng.path()));
+
+    let mut calls = vec![];
+    for path in &paths {
+        for method in [
+            "normalize",
+            "dirname",
+            "basename",
+            "extname",
+            "isAbsolute",
+            "parse",
+            "formatParse",
+        ] {
+            calls.push(Call::new(method, &[path]));
+        }
+        let other = &paths[rng.below(paths.len())];
+        calls.push(Call::new("basename", &[path, &posix::extname(other)]));
+        calls.push(Call::new("join", &[path, other]));
+        calls.push(Call::new("resolve", &[path, other]));
+        calls.push(Call::new("relative", &[path, other]));
+        let third = &paths[rng.below(paths.len())];
+        calls.push(Call::new("join", &[other, path, third]));
+        calls.push(Call::new("resolve", &["/base", path, third]));
+    }
+    calls
+}
+
+#[test]
+#[ignore = "needs a local node, run with `cargo test --test differential -- --ignored`"]
+fn posix_matches_node() {
+    if !node_available() {
+        eprintln!("skipping: `node` is not installed");
+        return;
+    }
+
+    let seed = env_number("NODEJS_PATH_DIFFERENTIAL_SEED", 0x9e37_79b9_7f4a_7c15).max(1);
+    let count = env_number("NODEJS_PATH_DIFFERENTIAL_CASES", 2_000);
+    let found = divergences(&calls(&mut Rng(seed), count));
+
+    let mut report = vec![];
+    let mut seen = vec![];
+    for (call, actual, expected) in &found {
+        let minimized = minimize(call);
+        if seen.contains(&minimized) {
+            continue;
+        }
+        let (actual_min, expected_min) = (
+            minimized.run(),
+            run_in_node(std::slice::from_ref(&minimized)).remove(0),
+        );
+        report.push(format!(
+            "{}({:?})\n  rust: {}\n  node: {}\n  minimized: {}({:?})\n    rust: {}\n    node: {}",
+            call.method,
+            call.args,
+            actual,
+            expected,
+            minimized.method,
+            minimized.args,
+            actual_min,
+            expected_min
+        ));
+        seen.push(minimized);
+    }
+
+    assert!(
+        report.is_empty(),
+        "{} divergences from node (seed {}), {} after minimizing:\n{}",
+        found.len(),
+        seed,
+        report.len(),
+        report.join("\n")
+    );
+}
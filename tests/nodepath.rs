@@ -0,0 +1,148 @@
+use std::{
+    io::Write,
+    process::{Command, Output, Stdio},
+};
+
+fn nodepath(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_nodepath"))
+        .args(args)
+        .output()
+        .unwrap()
+}
+
+fn stdout(args: &[&str]) -> String {
+    let output = nodepath(args);
+    assert!(
+        output.status.success(),
+        "nodepath {:?} failed: {:?}",
+        args,
+        output
+    );
+    String::from_utf8(output.stdout).unwrap()
+}
+
+#[test]
+fn commands() {
+    assert_eq!(
+        stdout(&["join", "/foo", "bar", "baz/asdf", "quux", ".."]),
+        "/foo/bar/baz/asdf\n"
+    );
+    assert_eq!(stdout(&["join"]), ".\n");
+    assert_eq!(stdout(&["resolve", "/foo/bar", "./baz"]), "/foo/bar/baz\n");
+    assert_eq!(
+        stdout(&[
+            "relative",
+            "/data/orandea/test/aaa",
+            "/data/orandea/impl/bbb"
+        ]),
+        "../../impl/bbb\n"
+    );
+    assert_eq!(
+        stdout(&["normalize", "/foo/bar//baz/asdf/quux/.."]),
+        "/foo/bar/baz/asdf\n"
+    );
+    assert_eq!(
+        stdout(&["dirname", "/foo/bar/baz/asdf/quux"]),
+        "/foo/bar/baz/asdf\n"
+    );
+    assert_eq!(stdout(&["basename", "/foo/quux.html"]), "quux.html\n");
+    assert_eq!(stdout(&["basename", "/foo/quux.html", ".html"]), "quux\n");
+    assert_eq!(stdout(&["extname", "index.coffee.md"]), ".md\n");
+    assert_eq!(stdout(&["is-absolute", "qux/"]), "false\n");
+    assert_eq!(
+        stdout(&["format", "--root", "/", "--name", "index", "--ext", ".html"]),
+        "/index.html\n"
+    );
+}
+
+#[test]
+fn parse() {
+    assert_eq!(
+        stdout(&["parse", "--json", "/home/user/dir/file.txt"]),
+        "{\"root\":\"/\",\"dir\":\"/home/user/dir\",\"base\":\"file.txt\",\"ext\":\".txt\",\"name\":\"file\"}\n"
+    );
+    assert_eq!(
+        stdout(&["parse", "--json", "a\"b\\c"]),
+        "{\"root\":\"\",\"dir\":\"\",\"base\":\"a\\\"b\\\\c\",\"ext\":\"\",\"name\":\"a\\\"b\\\\c\"}\n"
+    );
+    assert_eq!(
+        stdout(&["parse", "/home/file.txt"]),
+        "root: /\ndir: /home\nbase: file.txt\next: .txt\nname: file\n"
+    );
+}
+
+#[test]
+fn cwd_override() {
+    assert_eq!(
+        stdout(&["--cwd", "/tmp", "resolve", "a", "../b"]),
+        "/tmp/b\n"
+    );
+    assert_eq!(
+        stdout(&["--cwd", "/tmp", "relative", "a", "/tmp/b"]),
+        "../b\n"
+    );
+
+    let cwd = std::env::current_dir().unwrap();
+    let cwd = cwd.to_str().unwrap();
+    assert_eq!(
+        stdout(&["--cwd", "rel", "resolve", "x"]),
+        format!("{}/rel/x\n", cwd)
+    );
+    assert_eq!(stdout(&["--cwd", "rel/..", "relative", "a", cwd]), "..\n");
+}
+
+#[test]
+fn flavors() {
+    assert_eq!(stdout(&["--win32", "is-absolute", "C:\\foo"]), "true\n");
+    assert_eq!(stdout(&["--posix", "is-absolute", "C:\\foo"]), "false\n");
+    assert_eq!(stdout(&["--win32", "dirname", "C:\\foo\\bar"]), "C:\\foo\n");
+    assert_eq!(
+        stdout(&["--win32", "dirname", "\\\\server\\share"]),
+        "\\\\server\\share\n"
+    );
+
+    let output = nodepath(&["--win32", "join", "a", "b"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stderr).unwrap(),
+        "nodepath: win32 join is not implemented yet\n"
+    );
+}
+
+#[test]
+fn usage_errors() {
+    assert_eq!(nodepath(&[]).status.code(), Some(2));
+    assert_eq!(nodepath(&["frobnicate"]).status.code(), Some(2));
+    assert_eq!(nodepath(&["relative", "a"]).status.code(), Some(2));
+    assert_eq!(nodepath(&["--cwd"]).status.code(), Some(2));
+    assert!(nodepath(&["--help"]).status.success());
+}
+
+#[test]
+fn batch() {
+    let mut child = Command::new(env!("CARGO_BIN_EXE_nodepath"))
+        .args(["--cwd", "/srv", "--batch"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"join\ta\tb\nresolve\tx\n--cwd\t/tmp\tresolve\tx\nparse\t--json\t/a.b\nparse\t/a.b\n--win32\tis-absolute\tC:/\nbogus\nrelative\ta\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap(),
+        concat!(
+            "a/b\n/srv/x\n/tmp/x\n",
+            "{\"root\":\"/\",\"dir\":\"/\",\"base\":\"a.b\",\"ext\":\".b\",\"name\":\"a\"}\n",
+            "{\"root\":\"/\",\"dir\":\"/\",\"base\":\"a.b\",\"ext\":\".b\",\"name\":\"a\"}\n",
+            "true\n",
+            "\0error: unknown command `bogus`\n",
+            "\0error: relative expects <from> <to>\n",
+        )
+    );
+}